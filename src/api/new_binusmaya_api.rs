@@ -1,10 +1,9 @@
-use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use chrono::NaiveDate;
 use reqwest::header::{
     HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE, HOST, ORIGIN, REFERER,
     USER_AGENT,
 };
 use serde::{Deserialize, Serialize};
-use std::fmt;
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -21,21 +20,21 @@ pub struct AcademicPeriod {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RoleCategory {
-    name: String,
+    pub name: String,
     user_code: String,
     role_id: String,
     role_type: String,
     role_organization_id: String,
     academic_career_id: String,
     academic_career: String,
-    academic_career_desc: String,
+    pub academic_career_desc: String,
     #[serde(skip_deserializing)]
     institution_id: Option<String>,
     institution: String,
-    institution_desc: String,
+    pub institution_desc: String,
     academic_program: String,
-    academic_program_desc: String,
-    is_primary: bool,
+    pub academic_program_desc: String,
+    pub is_primary: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -69,7 +68,7 @@ pub struct SchedulePayload {
 pub struct RoleCategories {
     #[serde(skip_deserializing)]
     name: String,
-    roles: Vec<RoleCategory>,
+    pub roles: Vec<RoleCategory>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -77,235 +76,172 @@ pub struct RoleCategories {
 pub struct UserProfile {
     #[serde(skip_deserializing)]
     user_id: String,
-    #[serde(skip_deserializing)]
-    full_name: String,
-    #[serde(skip_deserializing)]
-    person_code: String,
-    #[serde(skip_deserializing)]
-    email: String,
-    #[serde(skip_deserializing)]
-    user_picture_url: String,
-    #[serde(skip_deserializing)]
-    xP_point: f32,
+    #[serde(default)]
+    pub full_name: String,
+    #[serde(default)]
+    pub person_code: String,
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub user_picture_url: Option<String>,
+    #[serde(default)]
+    pub xP_point: f32,
     #[serde(skip_deserializing)]
     category_list: Vec<String>,
 
-    role_categories: Vec<RoleCategories>,
+    pub role_categories: Vec<RoleCategories>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+impl UserProfile {
+    /// The primary role of the user, or the first one if none of them is primary
+    pub fn active_role(&self) -> Option<&RoleCategory> {
+        let mut roles = self.role_categories.iter().flat_map(|role_category| role_category.roles.iter());
+
+        roles.clone().find(|role| role.is_primary).or_else(|| roles.next())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CustomParam {
     pub class_id: String,
     pub class_session_id: String,
     pub session_number: u8,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub class_session_content_id: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduleDetails {
     pub date_start: String,
     pub date_end: String,
     pub title: String,
     pub content: String,
-    #[serde(skip_deserializing)] pub location: Option<String>,
+    #[serde(skip)] pub location: Option<String>,
     pub location_value: Option<String>,
-    #[serde(skip_deserializing)] pub schedule_type: String,
+    #[serde(skip)] pub schedule_type: String,
     pub custom_param: CustomParam,
     pub class_delivery_mode: String,
-    #[serde(skip_deserializing)] pub delivery_mode: String,
-    #[serde(skip_deserializing)] pub delivery_mode_desc: String,
-    #[serde(skip_deserializing)] pub academic_career_desc: String,
-    #[serde(skip_deserializing)] pub institution_desc: String,
-    #[serde(skip_deserializing)] pub organization_role_id: String,
+    #[serde(skip)] pub delivery_mode: String,
+    #[serde(skip)] pub delivery_mode_desc: String,
+    #[serde(skip)] pub academic_career_desc: String,
+    #[serde(skip)] pub institution_desc: String,
+    #[serde(skip)] pub organization_role_id: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Schedule {
     #[serde(rename(deserialize = "Schedule"))]
     pub schedule: Vec<ScheduleDetails>,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub date_start: String,
 }
 
-impl fmt::Display for Schedule {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for class in &self.schedule {
-            match &class.location_value {
-                Some(location) => {
-                    write!(f, "> Class Title: **{}**\n> Subject: **{}**\n> Start : **{}**\n> End: **{}**\n> Session: **{}**\n> Class Delivery Mode: **{}**\n> Location: **{}**\n> [Session link](https://newbinusmaya.binus.ac.id/lms/course/{}/session/{})\n\n", 
-                        class.title, class.content.clone(), 
-                        NaiveDateTime::parse_from_str(class.date_start.as_str(), "%FT%X").unwrap(), 
-                        NaiveDateTime::parse_from_str(class.date_end.as_str(), "%FT%X").unwrap(), 
-                        class.custom_param.session_number, 
-                        class.class_delivery_mode,
-                        location,
-                        class.custom_param.class_id,
-                        class.custom_param.class_session_id
-                    )?;
-                },
-                None => {
-                    write!(f, "> Class Title: **{}**\n> Subject: **{}**\n> Start : **{}**\n> End: **{}**\n> Session: **{}**\n> Class Delivery Mode: **{}**\n> [Session link](https://newbinusmaya.binus.ac.id/lms/course/{}/session/{})\n\n", 
-                        class.title, class.content.clone(), 
-                        NaiveDateTime::parse_from_str(class.date_start.as_str(), "%FT%X").unwrap(), 
-                        NaiveDateTime::parse_from_str(class.date_end.as_str(), "%FT%X").unwrap(), 
-                        class.custom_param.session_number, 
-                        class.class_delivery_mode,
-                        class.custom_param.class_id,
-                        class.custom_param.class_session_id
-                    )?;
-                },
-            }
-        }
-
-        Ok(())
-    }
-}
-
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ClassSessionProgress {
-    #[serde(skip_deserializing)]
-    completed: u8,
-    #[serde(skip_deserializing)]
-    in_progress: u8,
-    #[serde(skip_deserializing)]
-    not_started: u8,
+    #[serde(default)]
+    pub completed: u8,
+    #[serde(default)]
+    pub in_progress: u8,
+    #[serde(default)]
+    pub not_started: u8,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Lecturer {
-    #[serde(skip_deserializing)]
-    id: String,
-    #[serde(skip_deserializing)]
-    name: String,
-    #[serde(skip_deserializing)]
-    picture_url: String,
-    #[serde(skip_deserializing)]
-    role: String,
-    #[serde(skip_deserializing)]
-    user_code: String,
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub picture_url: Option<String>,
+    #[serde(default)]
+    pub role: String,
+    #[serde(default)]
+    pub user_code: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Resource {
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub android_redirect_url: Option<String>,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub assessment_type: Option<String>,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub due_date: String,
     pub duration: Option<String>,
     pub id: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub index: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub ios_redirect_url: Option<String>,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub is_open: bool,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub is_overdue: bool,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub last_updated_date: String,
     pub name: String,
     pub progress_stamp: u8,
     pub progress_status: u8,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub resource_last_updated_date: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub resource_status: String,
     pub resource_type: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub thumbnail: Option<String>,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub times_accessed: u8,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub token: Option<String>,
 
     #[serde(rename(deserialize = "type"))]
     pub material_type: Option<String>,
-
-    #[serde(skip_deserializing)]
     pub url: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+impl Resource {
+    pub fn is_completed(&self) -> bool {
+        self.progress_status == 2 || self.progress_stamp == 1
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(transparent)]
 pub struct ResourceList {
     pub list: Vec<Resource>,
 }
 
-impl fmt::Display for ResourceList {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for resource in &self.list
-         {
-            let duration = {
-                if let Some(i) = resource.duration.clone() {
-                    (i.parse::<u32>().unwrap() / 60).to_string()
-                } else {
-                    "?".to_string()
-                }
-            };
-
-            let progess_status = {
-                if resource.progress_status == 2 || resource.progress_stamp == 1 {
-                    "Completed"
-                } else if resource.progress_status == 1 {
-                    "In progress"
-                } else {
-                    "Not started"
-                }
-            };
-
-            write!(
-                f,
-                "> Name: **{}**\n> Duration: **{} min**\n> Type: **{}**\n> Status: **{}**\n\n",
-                resource.name, duration, resource.resource_type, progess_status
-            )?;
-        }
-
-        Ok(())
-    }
-}
-
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct SubTopics {
-    subtopics: Vec<String>,
-}
-
-impl fmt::Display for SubTopics {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for topic in &self.subtopics {
-            write!(f, "- {}\n", topic)?;
-        }
-        Ok(())
-    }
+    pub subtopics: Vec<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionDetails {
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub class_delivery_mode: String,
     pub class_session_progress: ClassSessionProgress,
     pub course_sub_topic: SubTopics,
@@ -313,35 +249,35 @@ pub struct SessionDetails {
     pub date_start: String,
     pub delivery_mode: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub delivery_mode_desc: String,
     pub end_date_session_utc: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub is_ended: bool,
     pub join_url: Option<String>,
 
-    #[serde(skip_deserializing)]
+    #[serde(default)]
     pub lecturers: Vec<Lecturer>,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub meeting_end: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub meeting_start: String,
     pub resources: ResourceList,
     pub session_number: u8,
     pub start_date_session_utc: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub status: Option<String>,
     pub topic: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     pub total_resource: u8,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Class {
     pub class_Code: String,
@@ -351,7 +287,7 @@ pub struct Class {
     pub ssr_component: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(transparent)]
 pub struct ClassVec {
     pub list: Vec<Class>,
@@ -394,30 +330,15 @@ pub struct ClassDetails {
     pub ssr_component: String,
 }
 
-impl fmt::Display for ClassVec {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.list.is_empty() {
-            write!(f, "No active class")?;
-        } else {
-            for class in &self.list {
-                write!(f, "> Class code: **{}**\n> Course code: **{}**\n> Course name: **{}**\n> Class component: **{}**\n\n", 
-                    class.class_Code, class.course_code, class.course_name, class.ssr_component)?;
-            }
-        }
-
-        Ok(())
-    }
-}
-
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SimpleLecturer {
-    #[serde(skip_deserializing)]
-    id: String,
-    #[serde(skip_deserializing)]
-    name: String,
-    #[serde(skip_deserializing)]
-    picture_url: String,
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub picture_url: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -434,7 +355,7 @@ pub struct Course {
     
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SimpleResource {
     duration: Option<String>,
@@ -442,105 +363,71 @@ pub struct SimpleResource {
     r#type: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OngoingClass {
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     academic_career_desc: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     class_campus_name: String,
     #[serde(skip)]
     class_code: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     class_delivery_mode: String,
-    class_id: String,
+    pub class_id: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     class_room_number: Option<String>,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     course_code: String,
-    course_component: String,
+    pub course_component: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     course_id: String,
-    course_name: String,
-    date_end: String,
+    pub course_name: String,
+    pub date_end: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     date_start: String,
-    delivery_mode: String,
+    pub delivery_mode: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     delivery_mode_desc: String,
     id: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     institution_desc: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     is_ended: bool,
 
-    #[serde(skip_deserializing)]
-    lecturers: Vec<SimpleLecturer>,
+    #[serde(default)]
+    pub lecturers: Vec<SimpleLecturer>,
     meeting_start: String,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     resource_id: Option<String>,
-    resources: Vec<Option<SimpleResource>>,
-    session_id: String,
-    session_number: u8,
+    pub resources: Vec<Option<SimpleResource>>,
+    pub session_id: String,
+    pub session_number: u8,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     session_progress: u8,
 
-    #[serde(skip_deserializing)]
+    #[serde(skip)]
     url: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct OngoingClasses {
     pub ongoing_classes: Vec<OngoingClass>,
 }
 
-impl fmt::Display for OngoingClasses {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for ongoing_class in &self.ongoing_classes {
-            let progress_status = {
-                if ongoing_class.resources.is_empty() {
-                    "Completed"
-                } else {
-                    "Incomplete"
-                }
-            };
-
-            let now = chrono::offset::Local::now();
-            let end_date = Local
-                .from_local_datetime(
-                    &NaiveDateTime::parse_from_str(ongoing_class.date_end.as_str(), "%FT%X")
-                        .unwrap(),
-                )
-                .unwrap();
-            let time_left = end_date - now;
-            write!(f, "> Class Component: **{}**\n> Course Name: **{}**\n> Time Left: **{} min**\n> Session: **{}**\n> Delivery Mode: **{}**\n> Status: **{}**\n> [Session Link](https://newbinusmaya.binus.ac.id/lms/course/{}/session/{})\n\n",
-				ongoing_class.course_component, 
-				ongoing_class.course_name, 
-				time_left.num_minutes(),
-				ongoing_class.session_number,
-				ongoing_class.delivery_mode,
-				progress_status,
-				ongoing_class.class_id,
-				ongoing_class.session_id
-			)?;
-        }
-        Ok(())
-    }
-}
-
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpcomingClass {
     #[serde(skip)]
@@ -553,21 +440,21 @@ pub struct UpcomingClass {
 
     #[serde(skip)]
     class_delivery_mode: String,
-    class_id: String,
+    pub class_id: String,
 
     #[serde(skip)]
     class_room_number: Option<String>,
 
     #[serde(skip)]
     course_code: String,
-    course_component: String,
+    pub course_component: String,
 
     #[serde(skip)]
     course_id: String,
-    course_name: String,
-    date_end: String,
-    date_start: String,
-    delivery_mode: String,
+    pub course_name: String,
+    pub date_end: String,
+    pub date_start: String,
+    pub delivery_mode: String,
 
     #[serde(skip)]
     delivery_mode_desc: String,
@@ -583,10 +470,10 @@ pub struct UpcomingClass {
 
     #[serde(skip)]
     is_has_ongoing_class: bool,
-    join_url: Option<String>,
+    pub join_url: Option<String>,
 
-    #[serde(skip)]
-    lecturers: Vec<SimpleLecturer>,
+    #[serde(default)]
+    pub lecturers: Vec<SimpleLecturer>,
 
     #[serde(skip)]
     meeting_start: String,
@@ -596,31 +483,13 @@ pub struct UpcomingClass {
 
     #[serde(skip)]
     resources: Vec<Option<SimpleResource>>,
-    session_id: String,
-    session_number: u8,
+    pub session_id: String,
+    pub session_number: u8,
 
     #[serde(skip)]
     session_progress: u8,
 }
 
-impl fmt::Display for UpcomingClass {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let start_datetime =
-            NaiveDateTime::parse_from_str(self.date_start.as_str(), "%FT%X").unwrap();
-        write!(f, "**Class Zoom Link**\n{}\n\n**Session Info**\n> Class Component: **{}**\n> Course Name: **{}**\n> Time Start: **{}**\n> Session: **{}**\n> Delivery Mode: **{}**\n> [Session link](https://newbinusmaya.binus.ac.id/lms/course/{}/session/{})\n",
-			self.join_url.clone().unwrap_or("No link".to_string()), 
-			self.course_component, 
-			self.course_name, 
-			start_datetime,
-			self.session_number, 
-			self.delivery_mode,
-			self.class_id,
-			self.session_id
-		)?;
-        Ok(())
-    }
-}
-
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OngoingClassResponse {
@@ -640,16 +509,16 @@ pub struct NewBinusmayaAPI {
     pub token: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Announcement {
-    academic_career_desc: String,
+    pub academic_career_desc: String,
     announcement_master_id: String,
     pub id: String,
-    title: String,
-    start_date: String,
-    end_date: String,
-    #[serde(skip)] is_read: bool,
+    pub title: String,
+    pub start_date: String,
+    pub end_date: String,
+    #[serde(default)] pub is_read: bool,
     #[serde(skip)] is_mandatory: bool,
     #[serde(skip)] link_url: Option<String>,
 }
@@ -663,16 +532,6 @@ pub struct AnnouncementResponse {
     pub total_data: u8,
 }
 
-impl fmt::Display for AnnouncementResponse {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (i, announcement) in self.announcements.clone().into_iter().enumerate() {
-            write!(f, "{}. **{}**\n", i + 1, announcement.title)?;
-        }
-
-        Ok(())
-    }
-}
-
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AnnouncementDetails {
@@ -681,8 +540,8 @@ pub struct AnnouncementDetails {
     pub content: String,
     end_date: String,
     institution_desc: Option<String>,
-    is_mandatory: bool,
-    link_url: Option<String>,
+    pub is_mandatory: bool,
+    pub link_url: Option<String>,
     start_date: String,
     pub title: String,
 }
@@ -834,11 +693,37 @@ impl NewBinusmayaAPI {
 			.get(format!("https://apim-bm7-prod.azure-api.net/func-bm7-course-prod/ClassSession/Session/{}/Resource/Student", session_id))
 			.query(&[("isWeb", "true")])
 			.send().await?
-			.json::<SessionDetails>().await.expect("Something's wrong when parsing response");
+			.json::<SessionDetails>().await?;
 
         Ok(session_details)
     }
 
+    /// Downloads a resource file, returns its content type and content
+    /// Downloads the file of a resource, `None` is returned as soon as it turns out to be over `max_size` bytes
+    pub async fn download_resource(&self, url: &str, max_size: usize) -> Result<Option<(Option<String>, Vec<u8>)>, reqwest::Error> {
+        let mut response = reqwest::get(url).await?.error_for_status()?;
+        if matches!(response.content_length(), Some(content_length) if content_length > max_size as u64) {
+            return Ok(None);
+        }
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(|content_type| content_type.to_string());
+
+        // the content length may be missing or wrong, so the size is checked again while downloading
+        let mut content = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if content.len() + chunk.len() > max_size {
+                return Ok(None);
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        Ok(Some((content_type, content)))
+    }
+
     pub async fn get_classes(&self) -> Result<ClassVec, reqwest::Error> {
         let client = self.init_client().await;
         let res = client
@@ -860,7 +745,7 @@ impl NewBinusmayaAPI {
         let client = self.init_client().await;
         let response = client
 			.get(format!("https://apim-bm7-prod.azure-api.net/func-bm7-course-prod/ClassSession/Class/{}/Student", class_id))
-			.send().await.expect("something's wrong")
+			.send().await?
 			.json::<ClassDetails>().await?;
 
         Ok(response)
@@ -917,9 +802,21 @@ impl NewBinusmayaAPI {
         Ok(res)
     }
 
+    /// Fetches every page of the announcements
+    pub async fn get_all_announcements(&self) -> Result<Vec<Announcement>, reqwest::Error> {
+        let first_page = self.get_announcement(1).await?;
+        let mut announcements = first_page.announcements;
+
+        for page_number in 2..=first_page.max_page {
+            announcements.extend(self.get_announcement(page_number).await?.announcements);
+        }
+
+        Ok(announcements)
+    }
+
     pub async fn get_announcement_details(
         &self,
-        id: &String,
+        id: &str,
     ) -> Result<Option<AnnouncementDetails>, reqwest::Error> {
         let client = self.init_client().await;
         let res = client
@@ -1,4 +1,4 @@
-use std::{fmt::Display, collections::HashMap, io::Cursor, fs::File, path::PathBuf};
+use std::{error::Error, fmt::Display, collections::HashMap, io::Cursor, fs::File, path::PathBuf};
 
 use reqwest::{header::{HeaderMap, CONTENT_TYPE, HOST, HeaderValue, ORIGIN, REFERER, COOKIE, HeaderName, SET_COOKIE}, redirect::Policy};
 use serde::{Deserialize, Deserializer, Serialize};
@@ -7,7 +7,7 @@ use thirtyfour::Cookie;
 use crate::discord::discord::{UserBinusianData, UserCredential};
 
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct SATPoint {
 	pub activity_type: String,
@@ -16,45 +16,25 @@ pub struct SATPoint {
 	pub total_points: u8
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(transparent)]
 pub struct SATPoints {
 	pub sat_points: Vec<SATPoint>
 }
 
-impl Display for SATPoints {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for sat_point in &self.sat_points {
-			write!(f, "{} - **{}** point(s)\n", sat_point.activity_type, sat_point.points)?;
-		}
-
-		Ok(())
-    }
-}
-
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct ComServ {
-	community_service_type: String,
-	points: u8,
-	total_points: u8,
-	target_points: u8
+	pub community_service_type: String,
+	pub points: u8,
+	pub total_points: u8,
+	pub target_points: u8
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(transparent)]
 pub struct ComServList {
-	list: Vec<ComServ>
-}
-
-impl Display for ComServList {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for comserv in &self.list {
-			write!(f, "{} - **{}** hour(s)", comserv.community_service_type, comserv.points)?;
-		}
-
-		Ok(())
-    }
+	pub list: Vec<ComServ>
 }
 
 #[derive(Deserialize, Debug)]
@@ -80,7 +60,7 @@ pub struct Courses {
 	pub courses: Vec<Course>
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Assignment {
 	#[serde(rename = "AssignmentFrom")] pub assignment_from: String,
@@ -96,28 +76,12 @@ pub struct Assignment {
 	#[serde(rename = "webcontent")] pub web_content: String
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(transparent)]
 pub struct AssignmentList {
 	pub assignments: Vec<Assignment>
 }
 
-impl Display for AssignmentList {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		if self.assignments.is_empty() {
-			write!(f, "No assignment")?;
-			return Ok(());
-		}
-		
-		for assignment in &self.assignments {
-			write!(f, "> Title: **{}**\n> Due datetime: **{} {}**\n\n",
-				assignment.title, assignment.deadline_duration, assignment.deadline_time)?;
-		}
-
-		Ok(())
-    }
-}
-
 #[derive(Deserialize, Serialize, Debug)]
 pub struct BinusianData {
 	#[serde(rename = "ACAD_CAREER")] pub acad_career: String,
@@ -156,6 +120,31 @@ pub struct SessionStatus {
 	#[serde(rename = "SessionStatus")] pub session_status: u8
 }
 
+#[derive(Debug)]
+pub enum LoginError {
+    /// Binusmaya didn't log the account in with the credential
+    Rejected(String),
+    /// Binusmaya couldn't be reached or failed, logging in again later may work
+    Unavailable(String),
+}
+
+impl Display for LoginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rejected(reason) => write!(f, "Failed to log in: {}", reason),
+            Self::Unavailable(reason) => write!(f, "Failed to reach binusmaya: {}", reason),
+        }
+    }
+}
+
+impl Error for LoginError {}
+
+impl From<reqwest::Error> for LoginError {
+    fn from(e: reqwest::Error) -> Self {
+        LoginError::Unavailable(e.to_string())
+    }
+}
+
 #[derive(Debug)]
 pub struct OldBinusmayaAPI {
 	pub cookie: String
@@ -283,7 +272,7 @@ impl OldBinusmayaAPI {
 		binusmaya_api
 	}
 
-	pub async fn login(binusian_data: &UserBinusianData, user_credential: &UserCredential) -> Self {
+	pub async fn login(binusian_data: &UserBinusianData, user_credential: &UserCredential) -> Result<Self, LoginError> {
 		let mut params = HashMap::with_capacity(7);
 		params.insert("displayName", binusian_data.display_name.clone());
 		params.insert("userName", user_credential.email.clone());
@@ -304,30 +293,45 @@ impl OldBinusmayaAPI {
 			.header(HOST, HeaderValue::from_static("binusmaya.binus.ac.id"))
 			.form(&params)
 			.send()
-			.await.unwrap();
+			.await?;
+
+		if res.status().is_server_error() {
+			return Err(LoginError::Unavailable(format!("login responded with {}", res.status())));
+		}
 
-		let cookie = res.headers().get(SET_COOKIE).unwrap().to_str().unwrap().to_string();
+		let cookie = res.headers()
+			.get(SET_COOKIE)
+			.and_then(|cookie| cookie.to_str().ok())
+			.ok_or_else(|| LoginError::Rejected(format!("no cookie in login response ({})", res.status())))?
+			.to_string();
 
 		let binusmaya_api= OldBinusmayaAPI {
 			cookie
 		};
 		
-		binusmaya_api
+		Ok(binusmaya_api)
 	}
 
-	pub async fn download_assignment(&self, link: &str, file_path: &PathBuf) -> Result<(), reqwest::Error> {
-		let mut file = File::create(file_path).unwrap();
-
+	pub fn assignment_download_url(link: &str) -> String {
 		let mut assignment_url = String::from("https://binusmaya.binus.ac.id/services/ci/index.php/general/downloadDocument/");
 		assignment_url.push_str(link.replace("\\", "...").replace(" ", "%20").as_str());
 
+		assignment_url
+	}
+
+	pub async fn get_assignment_file(&self, link: &str) -> Result<Vec<u8>, reqwest::Error> {
 		let client = self.init_client().await;
-		let res= client
-			.get(assignment_url)
+		let res = client
+			.get(OldBinusmayaAPI::assignment_download_url(link))
 			.send()
 			.await?;
 
-		let mut content = Cursor::new(res.bytes().await?);
+		Ok(res.bytes().await?.to_vec())
+	}
+
+	pub async fn download_assignment(&self, link: &str, file_path: &PathBuf) -> Result<(), reqwest::Error> {
+		let mut file = File::create(file_path).unwrap();
+		let mut content = Cursor::new(self.get_assignment_file(link).await?);
 
 		std::io::copy(&mut content, &mut file).unwrap();
 
@@ -337,7 +341,8 @@ impl OldBinusmayaAPI {
 
 #[cfg(test)]
 mod tests {
-use crate::{discord::helper::update_cookie, consts::OLDBINUSMAYA_USER_DATA};
+use serenity::http::Http;
+use crate::{discord::helper::{get_old_binusmaya_api, load_old_binusmaya_user_data}, consts::OLDBINUSMAYA_USER_DATA};
 
 use super::*;
 	const COOKIE_VAL: &str = "PHPSESSID=j4f4hfv6lq17obfin5nbg3j926";
@@ -406,7 +411,7 @@ use super::*;
 
 		let res = binusmaya_api.get_individual_assignments("CHAR6013001", "021583", "2110", "LEC", "21679").await.unwrap();
 
-		println!("{}", res);
+		println!("{:#?}", res);
 	}
 
 	#[tokio::test]
@@ -422,24 +427,12 @@ use super::*;
 
 	#[tokio::test]
 	async fn update_cookie_test() {
-		let user_data = OLDBINUSMAYA_USER_DATA.clone();
-		let mut user_data_content = user_data.lock().await;
-		user_data_content.insert(123, COOKIE_VAL.to_string());
-		let cookie = user_data_content.get(&123).unwrap();
-		let mut binusmaya_api = OldBinusmayaAPI {
-			cookie: cookie.to_string()
-		};
-
-		let session_status = binusmaya_api.check_session().await.unwrap().session_status;
-		println!("{}", session_status);
+		load_old_binusmaya_user_data().await;
+		let account_key = OLDBINUSMAYA_USER_DATA.lock().await.keys().next().unwrap().clone();
+		let http = Http::new_with_token(&std::env::var("DISCORD_TOKEN").unwrap());
 
-		if session_status == 0 {
-			println!("update cookie");
-			binusmaya_api = update_cookie(&123, binusmaya_api).await;
-			println!("{:?}", binusmaya_api);
-			user_data_content.insert(123, binusmaya_api.cookie.clone());
-			println!("{:#?}", user_data_content);
-		}
+		let binusmaya_api = get_old_binusmaya_api(&http, &account_key).await.unwrap();
+		println!("{:?}", binusmaya_api);
 
 		let sat = binusmaya_api.get_sat().await.unwrap();
 		println!("{:#?}", sat);
@@ -451,6 +444,6 @@ use super::*;
 			cookie: COOKIE_VAL.to_string()
 		};
 
-		let _res = binusmaya_api.download_assignment(r"general_course_outline\course_outline\assignment\RS1\010612\2020100113534300000581_Assignment 1 (Minggu ke-4) (Individual).docx", &PathBuf::from("2020100113534300000581_Assignment 1 (Minggu ke-4) (Individual).docx")).await.unwrap();
+		let _res = binusmaya_api.download_assignment(r"general_course_outline\course_outline\assignment\RS1\010612\2020100113534300000581_Assignment 1 (Minggu ke-4) (Individual).docx", &std::env::temp_dir().join("2020100113534300000581_Assignment 1 (Minggu ke-4) (Individual).docx")).await.unwrap();
 	}
 }
\ No newline at end of file
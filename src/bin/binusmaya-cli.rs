@@ -0,0 +1,9 @@
+use std::{env, process};
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = binusmaya::cli::run(env::args().skip(1).collect()).await {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
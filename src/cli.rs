@@ -0,0 +1,351 @@
+//! `binusmaya-cli`, calls the BINUSMAYA APIs without going through Discord to debug API changes and for scripts
+
+use std::{env, error::Error, ops::Add};
+
+use chrono::{Duration, Local};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    api::{dropbox_api, new_binusmaya_api::NewBinusmayaAPI, old_binusmaya_api::OldBinusmayaAPI},
+    consts::{ACTIVE_ACCOUNT_FILE, DEFAULT_ACCOUNT, NEWBINUSMAYA_USER_FILE, OLDBINUSMAYA_USER_FILE},
+    discord::{
+        commands::new_binusmaya::schedule::parse_date,
+        discord::{AccountKey, ActiveAccountRecord, NewBinusmayaUserRecord, OldBinusmayaUserRecord},
+        helper::{decrypt_token, parse_records},
+        render,
+    },
+    locale::{format_date, Language},
+};
+
+pub const USAGE: &str = "\
+Usage: binusmaya-cli [options] <command> [arguments]
+
+Commands:
+  schedule [date]                  schedule of a date like 2022-01-05, tomorrow or monday, today by default
+  classes                          active classes
+  session <class id> [number]      a session of a class, the first session by default
+  ongoing                          ongoing sessions
+  upcoming                         the next session
+  announcements [title]            every announcement, or the ones with the title
+  sat                              SAT points
+  comserv                          community service hours
+  assignments [period] [course]    the academic periods and courses, or the assignments of a course
+
+Options:
+  --token <token>         new binusmaya bearer token, or set BINUSMAYA_TOKEN
+  --cookie <cookie>       old binusmaya cookie, or set BINUSMAYA_COOKIE
+  --user <user id>        use the stored account of a Discord user, needs DROPBOX_TOKEN and TOKEN_KEY like the bot
+  --account <name>        the stored account to use, the active account of the user by default
+  --language <en | id>    language of the output, English by default
+  --json                  print the API response as JSON
+  --help                  show this message";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    token: Option<String>,
+    cookie: Option<String>,
+    user: Option<u64>,
+    account: Option<String>,
+    language: Language,
+    json: bool,
+    help: bool,
+}
+
+/// Splits the options from the command and its arguments, options take `--name value` or `--name=value`
+fn parse_args(args: Vec<String>) -> Result<(Options, Vec<String>), String> {
+    let mut options = Options::default();
+    let mut positional: Vec<String> = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+
+        match name.as_str() {
+            "--json" => options.json = true,
+            "--help" | "-h" => options.help = true,
+            "--token" | "--cookie" | "--user" | "--account" | "--language" => {
+                let value = inline_value.or_else(|| args.next()).ok_or_else(|| format!("{} needs a value", name))?;
+                match name.as_str() {
+                    "--token" => options.token = Some(value),
+                    "--cookie" => options.cookie = Some(value),
+                    "--user" => options.user = Some(value.parse().map_err(|_| format!("{} is not a Discord user id", value))?),
+                    "--account" => options.account = Some(value),
+                    _ => options.language = value.parse().map_err(|_| format!("{} is not a supported language, use en or id", value))?,
+                }
+            }
+            _ if name.starts_with("--") => return Err(format!("Unknown option {}\n\n{}", name, USAGE)),
+            _ => positional.push(arg),
+        }
+    }
+
+    Ok((options, positional))
+}
+
+/// Prints `value` as JSON with `--json`, otherwise the text
+fn print<T: Serialize>(options: &Options, value: &T, text: impl FnOnce() -> String) -> Result<(), Box<dyn Error>> {
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        println!("{}", text().trim_end());
+    }
+
+    Ok(())
+}
+
+fn titled(title: String, body: String) -> String {
+    format!("{}\n\n{}", title, body)
+}
+
+/// The records of a user data file in dropbox, read in memory so the local files of a bot running in the same directory aren't touched
+async fn stored_records<T: DeserializeOwned + 'static>(file_name: &str) -> Result<Vec<T>, Box<dyn Error>> {
+    let content = dropbox_api::download_file(file_name.to_string()).await?.unwrap_or_default();
+
+    Ok(parse_records(file_name, &content).await)
+}
+
+/// The Discord user of `--user` and the account of `--account`, or the active account of the user
+async fn stored_account(options: &Options) -> Result<AccountKey, Box<dyn Error>> {
+    let user_id = options.user.ok_or("Pass a token, a cookie or the --user of a stored account")?;
+    let account = match &options.account {
+        Some(account) => account.clone(),
+        None => stored_records::<ActiveAccountRecord>(ACTIVE_ACCOUNT_FILE)
+            .await?
+            .into_iter()
+            .find(|record| record.member_id == user_id)
+            .map_or_else(|| DEFAULT_ACCOUNT.to_string(), |record| record.account),
+    };
+
+    Ok((user_id, account))
+}
+
+async fn new_binusmaya_api(options: &Options) -> Result<NewBinusmayaAPI, Box<dyn Error>> {
+    if let Some(token) = options.token.clone().or_else(|| env::var("BINUSMAYA_TOKEN").ok()) {
+        return Ok(NewBinusmayaAPI { token });
+    }
+
+    let (user_id, account) = stored_account(options).await?;
+    let record = stored_records::<NewBinusmayaUserRecord>(NEWBINUSMAYA_USER_FILE)
+        .await?
+        .into_iter()
+        .find(|record| record.member_id == user_id && record.account_name() == account)
+        .ok_or_else(|| format!("{} has no new binusmaya account named {}", user_id, account))?;
+
+    if record.last_registered.add(Duration::weeks(52)) < Local::now() {
+        return Err(format!("The new binusmaya token of the {} account has expired", account).into());
+    }
+
+    let (token, _) = decrypt_token(&record.auth)?;
+
+    Ok(NewBinusmayaAPI { token })
+}
+
+async fn old_binusmaya_api(options: &Options) -> Result<OldBinusmayaAPI, Box<dyn Error>> {
+    if let Some(cookie) = options.cookie.clone().or_else(|| env::var("BINUSMAYA_COOKIE").ok()) {
+        return Ok(OldBinusmayaAPI { cookie });
+    }
+
+    let (user_id, account) = stored_account(options).await?;
+    let record = stored_records::<OldBinusmayaUserRecord>(OLDBINUSMAYA_USER_FILE)
+        .await?
+        .into_iter()
+        .find(|record| record.member_id == user_id && record.account_name() == account)
+        .ok_or_else(|| format!("{} has no old binusmaya account named {}", user_id, account))?;
+
+    Ok(OldBinusmayaAPI::login(&record.binusian_data, &record.user_credential).await?)
+}
+
+/// A field of a course in the old binusmaya course menu list
+fn course_field<'a>(course: &'a Value, field: &str) -> Result<&'a str, String> {
+    course[field].as_str().ok_or_else(|| format!("The course has no {}", field))
+}
+
+/// Lists the academic periods of the old binusmaya course menu list with their courses, numbered by the arguments of `assignments`
+fn describe_course_menu_list(course_menu_list: &Value) -> String {
+    let mut description = String::new();
+
+    for (i, period) in course_menu_list[0][3].as_array().into_iter().flatten().enumerate() {
+        description.push_str(&format!("{}. {}\n", i, period[1].as_str().unwrap_or_default()));
+
+        // the first two entries of a period are its id and name
+        for (j, course) in period.as_array().into_iter().flatten().enumerate().skip(2) {
+            description.push_str(&format!(
+                "   {}. {} - {}\n",
+                j,
+                course["CLASS_SECTION"].as_str().unwrap_or_default(),
+                course["COURSE_TITLE_LONG"].as_str().unwrap_or_default()
+            ));
+        }
+    }
+
+    description
+}
+
+/// Runs the command of `args`, the arguments after the binary name
+pub async fn run(args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let (options, positional) = parse_args(args)?;
+    let language = options.language;
+    let (command, args) = match positional.split_first() {
+        Some((command, args)) if !options.help => (command.as_str(), args),
+        _ => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+    };
+
+    match command {
+        "schedule" => {
+            let date = parse_date(&args.join(" "), Local::now().naive_local().date())
+                .map_err(|input| tr!(language, "schedule.invalid_date.body", input))?;
+            let schedule = new_binusmaya_api(&options).await?.get_schedule(&date).await?;
+
+            print(&options, &schedule, || titled(
+                tr!(language, "schedule.title", format_date(language, date)),
+                match &schedule {
+                    Some(schedule) => render::schedule(schedule, language),
+                    None => tr!(language, "schedule.holiday.body"),
+                },
+            ))
+        }
+        "classes" => {
+            let classes = new_binusmaya_api(&options).await?.get_classes().await?;
+
+            print(&options, &classes, || titled(tr!(language, "classes.title"), render::classes(&classes, language)))
+        }
+        "session" => {
+            let class_id = args.first().ok_or("Usage: binusmaya-cli session <class id> [number]")?;
+            let session_number: usize = match args.get(1) {
+                Some(number) => number.parse().map_err(|_| format!("{} is not a session number", number))?,
+                None => 1,
+            };
+            let binusmaya_api = new_binusmaya_api(&options).await?;
+            let class_details = binusmaya_api.get_class_details(class_id.clone()).await?;
+            let session = session_number
+                .checked_sub(1)
+                .and_then(|i| class_details.sessions.get(i))
+                .ok_or_else(|| format!("The class has {} session(s)", class_details.sessions.len()))?;
+            let session_details = binusmaya_api.get_resource(session.id.clone()).await?;
+
+            print(&options, &session_details, || titled(
+                format!("{}\n{}", tr!(language, "session.title", session_details.topic, session_details.session_number), render::session_url(class_id, &session.id)),
+                render::session_details(&session_details, language),
+            ))
+        }
+        "ongoing" => {
+            let ongoing_sessions = new_binusmaya_api(&options).await?.get_ongoing_sessions().await?.data;
+
+            print(&options, &ongoing_sessions, || render::ongoing_classes(&ongoing_sessions, language, Local::now().naive_local()))
+        }
+        "upcoming" => {
+            let upcoming_session = new_binusmaya_api(&options).await?.get_upcoming_sessions().await?;
+
+            print(&options, &upcoming_session, || titled(
+                tr!(language, "upcoming.title"),
+                match &upcoming_session {
+                    Some(upcoming_session) => render::upcoming_class(upcoming_session, language),
+                    None => tr!(language, "upcoming.none"),
+                },
+            ))
+        }
+        "announcements" => {
+            let search = args.join(" ").to_lowercase();
+            let announcements: Vec<_> = new_binusmaya_api(&options)
+                .await?
+                .get_all_announcements()
+                .await?
+                .into_iter()
+                .filter(|announcement| announcement.title.to_lowercase().contains(&search))
+                .collect();
+
+            print(&options, &announcements, || titled(tr!(language, "announcement.title"), render::announcements(&announcements, 1, language)))
+        }
+        "sat" => {
+            let sat = old_binusmaya_api(&options).await?.get_sat().await?;
+
+            print(&options, &sat, || titled(tr!(language, "sat.title"), render::sat_points(&sat, language)))
+        }
+        "comserv" => {
+            let comserv = old_binusmaya_api(&options).await?.get_comnunity_service().await?;
+
+            print(&options, &comserv, || titled(tr!(language, "comserv.title"), render::community_service(&comserv, language)))
+        }
+        "assignments" => {
+            let binusmaya_api = old_binusmaya_api(&options).await?;
+            let course_menu_list = binusmaya_api.get_course_menu_list().await?;
+
+            let (period, course) = match args {
+                [] => return print(&options, &course_menu_list, || describe_course_menu_list(&course_menu_list)),
+                [period, course] => match (period.parse::<usize>(), course.parse::<usize>()) {
+                    (Ok(period), Ok(course)) => (period, course),
+                    _ => return Err("The period and course are the numbers listed by `binusmaya-cli assignments`".into()),
+                },
+                _ => return Err("Usage: binusmaya-cli assignments [period] [course]".into()),
+            };
+
+            let chosen_course = &course_menu_list[0][3][period][course];
+            if !chosen_course.is_object() {
+                return Err(format!("There's no course {} in period {}, see `binusmaya-cli assignments`", course, period).into());
+            }
+
+            let (course_code, crse_id, strm, ssr_component, class_number) = (
+                course_field(chosen_course, "CRSE_CODE")?,
+                course_field(chosen_course, "CRSE_ID")?,
+                course_field(chosen_course, "STRM")?,
+                course_field(chosen_course, "SSR_COMPONENT")?,
+                course_field(chosen_course, "CLASS_NBR")?,
+            );
+            let individual = binusmaya_api.get_individual_assignments(course_code, crse_id, strm, ssr_component, class_number).await?;
+            let group = binusmaya_api.get_group_assignments(course_code, crse_id, strm, ssr_component, class_number).await?;
+
+            print(&options, &json!({ "individual": individual, "group": group }), || format!(
+                "{}\n\n{}",
+                titled(tr!(language, "assignment.individual_title"), render::assignments(&individual, language)),
+                titled(tr!(language, "assignment.group_title"), render::assignments(&group, language))
+            ))
+        }
+        _ => Err(format!("Unknown command {}\n\n{}", command, USAGE).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_test() {
+        let (options, positional) = parse_args(args(&["--json", "schedule", "--user", "42", "next", "week", "--language=id"])).unwrap();
+        assert_eq!(positional, args(&["schedule", "next", "week"]));
+        assert_eq!(options, Options { user: Some(42), language: Language::Indonesian, json: true, ..Options::default() });
+
+        let (options, positional) = parse_args(args(&["session", "class-1", "--token", "Bearer abc", "--account", "staff"])).unwrap();
+        assert_eq!(positional, args(&["session", "class-1"]));
+        assert_eq!(options.token.as_deref(), Some("Bearer abc"));
+        assert_eq!(options.account.as_deref(), Some("staff"));
+
+        assert!(parse_args(args(&["classes", "--token"])).is_err());
+        assert!(parse_args(args(&["classes", "--user", "me"])).is_err());
+        assert!(parse_args(args(&["classes", "--language", "fr"])).is_err());
+        assert!(parse_args(args(&["classes", "--verbose"])).is_err());
+    }
+
+    #[test]
+    fn describe_course_menu_list_test() {
+        let course_menu_list = json!([[null, null, null, [
+            ["2110", "Odd Semester 2021/2022",
+                { "CLASS_SECTION": "LA01", "COURSE_TITLE_LONG": "Algorithm and Programming" },
+                { "CLASS_SECTION": "LB02", "COURSE_TITLE_LONG": "Linear Algebra" }]
+        ]]]);
+
+        assert_eq!(describe_course_menu_list(&course_menu_list), "0. Odd Semester 2021/2022
+   2. LA01 - Algorithm and Programming
+   3. LB02 - Linear Algebra
+");
+    }
+}
@@ -1,4 +1,5 @@
-use crate::discord::discord::NewBinusmayaUserAuthInfo;
+use crate::{crypto::Keyring, locale::Language, discord::{archive::FileCache, discord::{AccountKey, GuildConfig, NewBinusmayaUserAuthInfo, OldBinusmayaUserAuthInfo}, helper::FetchedClasses, scheduler::TaskStatus}};
+use chrono::Duration;
 use magic_crypt::MagicCrypt256;
 use serenity::utils::Colour;
 use std::{collections::HashMap, env, sync::Arc};
@@ -8,19 +9,67 @@ pub const PRIMARY_COLOR: Colour = Colour::BLUE;
 
 pub const NEWBINUSMAYA_USER_FILE: &str = "user_data.csv";
 pub const OLDBINUSMAYA_USER_FILE: &str = "old_binusmaya_user_data.csv";
+pub const ACTIVE_ACCOUNT_FILE: &str = "active_account.csv";
+pub const GUILD_CONFIG_FILE: &str = "guild_config.csv";
+pub const CLASS_ROLE_FILE: &str = "class_role.csv";
+pub const COURSE_CHANNEL_FILE: &str = "course_channel.csv";
+pub const CLASS_SUBSCRIPTION_FILE: &str = "class_subscription.csv";
+pub const DISCOVERY_FILE: &str = "discovery.csv";
+pub const LEADERBOARD_FILE: &str = "leaderboard.csv";
+pub const XP_SNAPSHOT_FILE: &str = "xp_snapshot.csv";
+pub const USER_LANGUAGE_FILE: &str = "user_language.csv";
 pub const LOGIN_FILE: &str = "last_login.txt";
 
+/// Files that are uploaded to dropbox and downloaded when the bot starts
+pub const STORED_FILES: [&str; 11] = [
+    NEWBINUSMAYA_USER_FILE,
+    OLDBINUSMAYA_USER_FILE,
+    ACTIVE_ACCOUNT_FILE,
+    GUILD_CONFIG_FILE,
+    CLASS_ROLE_FILE,
+    COURSE_CHANNEL_FILE,
+    CLASS_SUBSCRIPTION_FILE,
+    DISCOVERY_FILE,
+    LEADERBOARD_FILE,
+    XP_SNAPSHOT_FILE,
+    USER_LANGUAGE_FILE,
+];
+
+pub const DEFAULT_ACCOUNT: &str = "default";
+pub const DEFAULT_PREFIX: &str = "=";
+/// The delimiter the framework splits command arguments with
+pub const ARG_DELIMITER: char = ';';
+
 pub const NEW_BINUSMAYA: &str = "https://newbinusmaya.binus.ac.id";
 pub const OLD_BINUSMAYA: &str = "https://binusmaya.binus.ac.id";
 
 pub const CHROME_SERVER_URL: &str = "http://localhost:9222";
 
 lazy_static! {
-    pub static ref NEWBINUSMAYA_USER_DATA: Arc<Mutex<HashMap<u64, NewBinusmayaUserAuthInfo>>> =
+    pub static ref NEWBINUSMAYA_USER_DATA: Arc<Mutex<HashMap<AccountKey, NewBinusmayaUserAuthInfo>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    pub static ref OLDBINUSMAYA_USER_DATA: Arc<Mutex<HashMap<AccountKey, OldBinusmayaUserAuthInfo>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    pub static ref ACTIVE_ACCOUNT: Arc<Mutex<HashMap<u64, String>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    pub static ref GUILD_CONFIG: Arc<Mutex<HashMap<u64, GuildConfig>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    /// Languages chosen by the users with `=language`, they take precedence over the guild language
+    pub static ref USER_LANGUAGE: Arc<Mutex<HashMap<u64, Language>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    pub static ref CLASS_SYNC_LOCK: Mutex<()> = Mutex::new(());
+    pub static ref CLASS_CACHE: Arc<Mutex<HashMap<u64, FetchedClasses>>> =
         Arc::new(Mutex::new(HashMap::new()));
-    pub static ref OLDBINUSMAYA_USER_DATA: Arc<Mutex<HashMap<u64, String>>> =
+    /// Question files of old binusmaya assignments by their student assignment id, up to 64 MB for an hour
+    pub static ref ASSIGNMENT_FILE_CACHE: Arc<Mutex<FileCache>> =
+        Arc::new(Mutex::new(FileCache::new(64 * 1024 * 1024, Duration::hours(1))));
+    /// Whether each background task is running and when it last ran, by task name
+    pub static ref TASK_STATUS: Arc<Mutex<HashMap<&'static str, TaskStatus>>> =
         Arc::new(Mutex::new(HashMap::new()));
     pub static ref CHROME_BINARY: Mutex<String> =
         Mutex::new(env::var("GOOGLE_CHROME_SHIM").unwrap());
     pub static ref MAGIC_CRYPT: MagicCrypt256 = new_magic_crypt!(env::var("SECRET_KEY").expect("expected SECRET KEY in env"), 256);
+    pub static ref OLD_MAGIC_CRYPT: Option<MagicCrypt256> = env::var("OLD_SECRET_KEY").ok().map(|key| new_magic_crypt!(key, 256));
+    pub static ref TOKEN_CRYPT: Keyring = Keyring::from_env("TOKEN_KEY", "OLD_TOKEN_KEY", b"new-binusmaya-token");
+    pub static ref PASSWORD_CRYPT: Keyring = Keyring::from_env("SECRET_KEY", "OLD_SECRET_KEY", b"old-binusmaya-password");
 }
@@ -0,0 +1,151 @@
+use std::{env, error::Error, fmt::Display};
+
+use aes_gcm::{
+    aead::{Aead, NewAead},
+    Aes256Gcm, Key, Nonce,
+};
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::Sha256;
+
+const NONCE_LEN: usize = 12;
+const CIPHERTEXT_PREFIX: &str = "v1:";
+
+#[derive(Debug)]
+pub struct CryptoError(pub String);
+
+impl Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to decrypt: {}", self.0)
+    }
+}
+
+impl Error for CryptoError {}
+
+pub struct Cipher {
+    cipher: Aes256Gcm,
+}
+
+impl Cipher {
+    /// Derives an AES-256-GCM key from `secret`, `info` separates keys used for different purposes
+    pub fn new(secret: &str, info: &[u8]) -> Self {
+        let mut key = [0u8; 32];
+        Hkdf::<Sha256>::new(None, secret.as_bytes())
+            .expand(info, &mut key)
+            .expect("32 bytes is a valid HKDF output length");
+
+        Cipher {
+            cipher: Aes256Gcm::new(Key::from_slice(&key)),
+        }
+    }
+
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(CIPHERTEXT_PREFIX)
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> String {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .expect("AES-GCM encryption doesn't fail on in-memory input");
+
+        let mut payload = nonce.to_vec();
+        payload.extend(ciphertext);
+
+        format!("{}{}", CIPHERTEXT_PREFIX, base64::encode(payload))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String, CryptoError> {
+        let encoded = value
+            .strip_prefix(CIPHERTEXT_PREFIX)
+            .ok_or_else(|| CryptoError("unknown ciphertext format".to_string()))?;
+        let payload = base64::decode(encoded).map_err(|e| CryptoError(e.to_string()))?;
+
+        if payload.len() <= NONCE_LEN {
+            return Err(CryptoError("ciphertext is too short".to_string()));
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptoError("wrong key or tampered ciphertext".to_string()))?;
+
+        String::from_utf8(plaintext).map_err(|e| CryptoError(e.to_string()))
+    }
+}
+
+/// A cipher for new ciphertexts and, while a key rotation is in progress, the cipher of the previous key
+pub struct Keyring {
+    current: Cipher,
+    previous: Option<Cipher>,
+}
+
+impl Keyring {
+    pub fn new(secret: &str, previous_secret: Option<&str>, info: &[u8]) -> Self {
+        Keyring {
+            current: Cipher::new(secret, info),
+            previous: previous_secret.map(|secret| Cipher::new(secret, info)),
+        }
+    }
+
+    pub fn from_env(key: &str, previous_key: &str, info: &[u8]) -> Self {
+        let secret = env::var(key).unwrap_or_else(|_| panic!("expected {} in env", key));
+        let previous_secret = env::var(previous_key).ok();
+
+        Keyring::new(&secret, previous_secret.as_deref(), info)
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> String {
+        self.current.encrypt(plaintext)
+    }
+
+    /// Returns the plaintext and whether it has to be re-encrypted with the current key
+    pub fn decrypt(&self, value: &str) -> Result<(String, bool), CryptoError> {
+        match self.current.decrypt(value) {
+            Ok(plaintext) => Ok((plaintext, false)),
+            Err(e) => match &self.previous {
+                Some(previous) => previous.decrypt(value).map(|plaintext| (plaintext, true)),
+                None => Err(e),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_test() {
+        let cipher = Cipher::new("secret", b"test");
+        let encrypted = cipher.encrypt("Bearer token");
+
+        assert!(Cipher::is_encrypted(&encrypted));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "Bearer token");
+    }
+
+    #[test]
+    fn decrypt_with_wrong_key_test() {
+        let encrypted = Cipher::new("secret", b"test").encrypt("Bearer token");
+
+        assert!(Cipher::new("other secret", b"test").decrypt(&encrypted).is_err());
+        assert!(Cipher::new("secret", b"other").decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn keyring_rotation_test() {
+        let old_keyring = Keyring::new("old secret", None, b"test");
+        let encrypted = old_keyring.encrypt("password");
+
+        let keyring = Keyring::new("new secret", Some("old secret"), b"test");
+        assert_eq!(keyring.decrypt(&encrypted).unwrap(), ("password".to_string(), true));
+
+        let reencrypted = keyring.encrypt("password");
+        assert_eq!(keyring.decrypt(&reencrypted).unwrap(), ("password".to_string(), false));
+        assert!(old_keyring.decrypt(&reencrypted).is_err());
+    }
+}
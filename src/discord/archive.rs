@@ -0,0 +1,208 @@
+use chrono::{DateTime, Duration, Local};
+use std::{
+    collections::HashMap,
+    io::{Cursor, Write},
+};
+
+use zip::{result::ZipResult, write::FileOptions, CompressionMethod, ZipWriter};
+
+/// Discord rejects attachments over 8 MB, some room is left for the zip headers
+pub const MAX_UPLOAD_SIZE: usize = 7 * 1024 * 1024;
+
+/// A downloaded file, its name and content
+pub type ArchiveFile = (String, Vec<u8>);
+
+/// Downloaded files kept for `ttl`, the oldest files are dropped once they're over `max_size` bytes in total
+pub struct FileCache {
+    files: HashMap<u32, (DateTime<Local>, ArchiveFile)>,
+    max_size: usize,
+    ttl: Duration,
+}
+
+impl FileCache {
+    pub fn new(max_size: usize, ttl: Duration) -> Self {
+        FileCache { files: HashMap::new(), max_size, ttl }
+    }
+
+    pub fn get(&mut self, key: u32) -> Option<ArchiveFile> {
+        self.remove_expired(Local::now());
+
+        self.files.get(&key).map(|(_, file)| file.clone())
+    }
+
+    /// Files over `max_size` bytes aren't kept
+    pub fn insert(&mut self, key: u32, file: ArchiveFile) {
+        self.insert_at(key, file, Local::now());
+    }
+
+    fn insert_at(&mut self, key: u32, file: ArchiveFile, now: DateTime<Local>) {
+        self.remove_expired(now);
+        self.files.remove(&key);
+        if file.1.len() > self.max_size {
+            return;
+        }
+
+        while self.size() + file.1.len() > self.max_size {
+            let oldest = self.files.iter().min_by_key(|(_, (cached_at, _))| *cached_at).map(|(key, _)| *key);
+            match oldest {
+                Some(oldest) => self.files.remove(&oldest),
+                None => break,
+            };
+        }
+
+        self.files.insert(key, (now, file));
+    }
+
+    fn remove_expired(&mut self, now: DateTime<Local>) {
+        let ttl = self.ttl;
+        self.files.retain(|_, (cached_at, _)| *cached_at + ttl > now);
+    }
+
+    fn size(&self) -> usize {
+        self.files.values().map(|(_, (_, content))| content.len()).sum()
+    }
+}
+
+/// Replaces the characters that aren't allowed in a file name on Windows or Linux
+pub fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_control() || "\\/:*?\"<>|".contains(c) { '_' } else { c })
+        .collect();
+
+    if sanitized.is_empty() { "file".to_string() } else { sanitized }
+}
+
+/// Appends a number to the name if another file already has it, e.g. `notes (2).pdf`
+fn unique_file_name(name: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|taken_name| taken_name.eq(name)) {
+        return name.to_string();
+    }
+
+    let (stem, extension) = match name.rfind('.') {
+        Some(index) if index > 0 => (&name[..index], &name[index..]),
+        _ => (name, ""),
+    };
+
+    (2..)
+        .map(|n| format!("{} ({}){}", stem, n, extension))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap()
+}
+
+/// The last segment of the url path, empty when the url has no path
+pub fn url_file_name(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let path = path.split_once("://").map_or(path, |(_, path)| path);
+
+    path.split_once('/').map_or("", |(_, path)| path.rsplit('/').next().unwrap_or_default())
+}
+
+pub fn is_uploadable(content: &[u8]) -> bool {
+    content.len() <= MAX_UPLOAD_SIZE
+}
+
+/// Groups the files into bundles that fit in one upload, files that aren't uploadable should be linked instead
+pub fn bundle_files(files: Vec<ArchiveFile>) -> Vec<Vec<ArchiveFile>> {
+    let mut bundles: Vec<Vec<ArchiveFile>> = Vec::new();
+    let mut taken_names = Vec::new();
+    let mut bundle_size = 0;
+
+    for (name, content) in files {
+        let name = unique_file_name(&sanitize_file_name(&name), &taken_names);
+        taken_names.push(name.clone());
+
+        if bundles.is_empty() || bundle_size + content.len() > MAX_UPLOAD_SIZE {
+            bundles.push(Vec::new());
+            bundle_size = 0;
+        }
+
+        bundle_size += content.len();
+        bundles.last_mut().unwrap().push((name, content));
+    }
+
+    bundles
+}
+
+pub fn zip_files(files: &[ArchiveFile]) -> ZipResult<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, content) in files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(content)?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Names the archive after its title, numbered when the files are split into several archives
+pub fn archive_name(title: &str, part: usize, parts: usize) -> String {
+    let title = sanitize_file_name(title);
+
+    if parts > 1 {
+        format!("{} (part {} of {}).zip", title, part, parts)
+    } else {
+        format!("{}.zip", title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_file_name_test() {
+        assert_eq!(sanitize_file_name("Session 1: Intro/Overview?.pdf"), "Session 1_ Intro_Overview_.pdf");
+        assert_eq!(sanitize_file_name("  "), "file");
+    }
+
+    #[test]
+    fn url_file_name_test() {
+        assert_eq!(url_file_name("https://example.com/files/week1.pptx?sv=2020&sig=x"), "week1.pptx");
+        assert_eq!(url_file_name("https://example.com/files/"), "");
+        assert_eq!(url_file_name("https://example.com"), "");
+    }
+
+    #[test]
+    fn bundle_files_test() {
+        let files = vec![
+            ("a.pdf".to_string(), vec![0; MAX_UPLOAD_SIZE / 2]),
+            ("a.pdf".to_string(), vec![0; MAX_UPLOAD_SIZE / 2]),
+            ("b.pdf".to_string(), vec![0; 10]),
+        ];
+        let bundles = bundle_files(files);
+
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(bundles[0][1].0, "a (2).pdf");
+        assert_eq!(bundles[1][0].0, "b.pdf");
+        assert!(!is_uploadable(&vec![0; MAX_UPLOAD_SIZE + 1]));
+    }
+
+    #[test]
+    fn file_cache_test() {
+        let now = Local::now();
+        let mut file_cache = FileCache::new(10, Duration::hours(1));
+        file_cache.insert_at(1, ("a.pdf".to_string(), vec![0; 4]), now - Duration::minutes(2));
+        file_cache.insert_at(2, ("b.pdf".to_string(), vec![0; 4]), now - Duration::minutes(1));
+        file_cache.insert_at(3, ("c.pdf".to_string(), vec![0; 4]), now);
+        file_cache.insert_at(4, ("d.pdf".to_string(), vec![0; 11]), now);
+
+        assert!(file_cache.get(1).is_none(), "the oldest file is dropped to stay under the size limit");
+        assert_eq!(file_cache.get(2).unwrap().0, "b.pdf");
+        assert!(file_cache.get(4).is_none(), "files over the size limit aren't kept");
+
+        file_cache.remove_expired(now + Duration::hours(1));
+        assert_eq!(file_cache.size(), 0);
+    }
+
+    #[test]
+    fn zip_files_test() {
+        let archive = zip_files(&[("a.txt".to_string(), b"hello".to_vec())]).unwrap();
+        let zip = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+
+        assert_eq!(zip.len(), 1);
+        assert_eq!(archive_name("COMP6047 Session 1", 2, 3), "COMP6047 Session 1 (part 2 of 3).zip");
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use serenity::{model::{interactions::message_component::ButtonStyle, prelude::*}, prelude::*};
+
+use crate::{
+    api::new_binusmaya_api::{AnnouncementDetails, NewBinusmayaAPI, SessionDetails},
+    consts::{CLASS_SUBSCRIPTION_FILE, PRIMARY_COLOR},
+    discord::{helper::{guild_language, new_binusmaya_api, read_records}, markdown::{html_to_markdown, split_text, EMBED_DESCRIPTION_LIMIT}, render, scheduler::{finish_task, start_task, CLASS_SUBSCRIPTION_TASK}},
+    locale::format_api_datetime,
+};
+
+/// How long before a session starts it's posted to the subscribed channel
+pub const NOTICE_BEFORE_START: i64 = 30;
+/// How often the sessions of the subscribed classes are checked, in minutes
+const CHECK_INTERVAL: i64 = 5;
+
+/// A guild channel that mirrors the sessions and announcements of a class
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ClassSubscription {
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub class_id: String,
+    pub course_name: String,
+    pub class_code: String,
+    /// Empty for the subscriptions made before announcements were mirrored
+    #[serde(default)]
+    pub course_code: String,
+    /// The registered member who agreed to let the bot read the class with their token
+    pub source_member_id: u64,
+    pub source_account: String,
+}
+
+impl ClassSubscription {
+    pub async fn source_api(&self) -> Option<NewBinusmayaAPI> {
+        new_binusmaya_api(&(self.source_member_id, self.source_account.clone())).await
+    }
+
+    fn key(&self) -> SubscriptionKey {
+        (self.channel_id, self.class_id.clone())
+    }
+
+    /// Binusmaya doesn't tie an announcement to a class, so it belongs to the class when its title mentions the course
+    fn mentions_course(&self, title: &str) -> bool {
+        let title = title.to_lowercase();
+
+        (!self.course_code.is_empty() && title.contains(&self.course_code.to_lowercase()))
+            || (!self.course_name.is_empty() && title.contains(&self.course_name.to_lowercase()))
+    }
+}
+
+/// The channel id and class id of a subscription
+type SubscriptionKey = (u64, String);
+
+fn notice_at(start: DateTime<Local>) -> DateTime<Local> {
+    start - Duration::minutes(NOTICE_BEFORE_START)
+}
+
+/// Whether the notice of a session that starts at `start` became due after the last check, so each session is posted once
+fn is_notice_due(start: DateTime<Local>, last_checked: DateTime<Local>, now: DateTime<Local>) -> bool {
+    let notice_at = notice_at(start);
+
+    notice_at > last_checked && notice_at <= now && start > now
+}
+
+fn session_start(session_details: &SessionDetails) -> Option<DateTime<Local>> {
+    let date_start = NaiveDateTime::parse_from_str(&session_details.date_start, "%FT%X").ok()?;
+
+    Local.from_local_datetime(&date_start).single()
+}
+
+/// Returns the id and start time of every session of the class
+async fn class_sessions(binusmaya_api: &NewBinusmayaAPI, class_id: &str) -> Result<Vec<(String, DateTime<Local>)>, reqwest::Error> {
+    let class_details = binusmaya_api.get_class_details(class_id.to_string()).await?;
+    let mut sessions = Vec::new();
+
+    for session in class_details.sessions {
+        let session_details = binusmaya_api.get_resource(session.id.clone()).await?;
+
+        if let Some(start) = session_start(&session_details) {
+            sessions.push((session.id, start));
+        }
+    }
+
+    Ok(sessions)
+}
+
+pub async fn post_session(ctx: &Context, subscription: &ClassSubscription, session_id: &str, session_details: &SessionDetails) -> serenity::Result<Message> {
+    let session_url = render::session_url(&subscription.class_id, session_id);
+    let language = guild_language(Some(GuildId(subscription.guild_id))).await.unwrap_or_default();
+
+    ChannelId(subscription.channel_id).send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title(format!("{} - {} {}", subscription.course_name, tr!(language, "session.session"), session_details.session_number))
+            .url(&session_url)
+            .colour(PRIMARY_COLOR)
+            .field(tr!(language, "session.topic"), &session_details.topic, false)
+            .field(tr!(language, "session.subtopics"), render::subtopics(&session_details.course_sub_topic), false)
+            .field(tr!(language, "session.starts_at"), format_api_datetime(language, &session_details.date_start), true)
+            .field(tr!(language, "session.delivery_mode"), &session_details.delivery_mode, true)
+            .field(tr!(language, "session.lecturers"), render::lecturer_names(&session_details.lecturers, language), false)
+            .footer(|f| f.text(tr!(language, "session.class_footer", subscription.class_code)))
+        );
+
+        if let Some(join_url) = &session_details.join_url {
+            m.components(|c| c.create_action_row(|ar| ar
+                .create_button(|b| b
+                    .label(tr!(language, "session.join"))
+                    .style(ButtonStyle::Link)
+                    .url(join_url)
+                )
+            ));
+        }
+
+        m
+    }).await
+}
+
+async fn post_announcement(ctx: &Context, subscription: &ClassSubscription, details: &AnnouncementDetails) -> serenity::Result<Message> {
+    let language = guild_language(Some(GuildId(subscription.guild_id))).await.unwrap_or_default();
+    // the whole announcement can be read with the announcement command, the channel only gets its first page
+    let content = split_text(&html_to_markdown(&details.content), EMBED_DESCRIPTION_LIMIT).into_iter().next().unwrap_or_default();
+
+    ChannelId(subscription.channel_id).send_message(&ctx.http, |m| {
+        m.embed(|e| {
+            e.title(&details.title)
+                .description(content)
+                .colour(PRIMARY_COLOR)
+                .field(tr!(language, "announcement.mandatory"), tr!(language, if details.is_mandatory { "common.yes" } else { "common.no" }), true)
+                .footer(|f| f.text(tr!(language, "session.class_footer", subscription.class_code)));
+
+            if let Some(link_url) = details.link_url.as_ref().filter(|link_url| !link_url.trim().is_empty()) {
+                e.field(tr!(language, "announcement.link"), link_url, true);
+            }
+
+            e
+        })
+    }).await
+}
+
+/// The session ids and start times of each class id and when they were fetched
+type SessionCache = HashMap<String, (DateTime<Local>, Vec<(String, DateTime<Local>)>)>;
+
+/// Posts the sessions whose notice became due after `last_checked`, returns until when the sessions were posted.
+/// The window stops before a session that couldn't be posted so it's tried again in the next check
+async fn post_due_sessions(
+    ctx: &Context,
+    subscription: &ClassSubscription,
+    binusmaya_api: &NewBinusmayaAPI,
+    session_cache: &mut SessionCache,
+    last_checked: DateTime<Local>,
+    now: DateTime<Local>,
+) -> DateTime<Local> {
+    let is_stale = session_cache
+        .get(&subscription.class_id)
+        .is_none_or(|(fetched_at, _)| *fetched_at + Duration::days(1) < now);
+
+    if is_stale {
+        match class_sessions(binusmaya_api, &subscription.class_id).await {
+            Ok(sessions) => {
+                session_cache.insert(subscription.class_id.clone(), (now, sessions));
+            }
+            Err(e) => {
+                eprintln!("Failed to get the sessions of class {}: {:?}", subscription.class_id, e);
+                return last_checked;
+            }
+        }
+    }
+
+    let mut due_sessions: Vec<(String, DateTime<Local>)> = session_cache[&subscription.class_id]
+        .1
+        .iter()
+        .filter(|(_, start)| is_notice_due(*start, last_checked, now))
+        .cloned()
+        .collect();
+    due_sessions.sort_by_key(|(_, start)| *start);
+
+    for (session_id, start) in due_sessions {
+        // fetched again so the join link is up to date
+        let result = match binusmaya_api.get_resource(session_id.clone()).await {
+            Ok(session_details) => post_session(ctx, subscription, &session_id, &session_details).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        if let Err(e) = result {
+            eprintln!("Failed to post session {} to {}: {}", session_id, subscription.channel_id, e);
+            return notice_at(start) - Duration::seconds(1);
+        }
+    }
+
+    now
+}
+
+/// Posts the new announcements of the class, returns the ids of its announcements that don't have to be posted anymore.
+/// The announcements found in the first check of a subscription are only remembered, so a restart doesn't post them again
+async fn post_new_announcements(
+    ctx: &Context,
+    subscription: &ClassSubscription,
+    binusmaya_api: &NewBinusmayaAPI,
+    seen_announcements: Option<&HashSet<String>>,
+) -> Result<HashSet<String>, reqwest::Error> {
+    let announcement_ids: Vec<String> = binusmaya_api
+        .get_all_announcements()
+        .await?
+        .into_iter()
+        .filter(|announcement| subscription.mentions_course(&announcement.title))
+        .map(|announcement| announcement.id)
+        .collect();
+
+    let seen_announcements = match seen_announcements {
+        Some(seen_announcements) => seen_announcements,
+        None => return Ok(announcement_ids.into_iter().collect()),
+    };
+
+    let mut posted = HashSet::new();
+    for announcement_id in announcement_ids {
+        if seen_announcements.contains(&announcement_id) {
+            posted.insert(announcement_id);
+            continue;
+        }
+
+        let result = match binusmaya_api.get_announcement_details(&announcement_id).await {
+            Ok(Some(details)) => post_announcement(ctx, subscription, &details).await.map(|_| ()).map_err(|e| e.to_string()),
+            Ok(None) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        // an announcement that couldn't be posted is left out so it's tried again in the next check
+        match result {
+            Ok(()) => {
+                posted.insert(announcement_id);
+            }
+            Err(e) => eprintln!("Failed to post announcement {} to {}: {}", announcement_id, subscription.channel_id, e),
+        }
+    }
+
+    Ok(posted)
+}
+
+/// Posts every session of the subscribed classes to their channel a while before it starts, and their new announcements
+pub async fn class_subscription_event(ctx: &Context) {
+    let mut session_cache: SessionCache = HashMap::new();
+    let mut last_checked: HashMap<SubscriptionKey, DateTime<Local>> = HashMap::new();
+    let mut seen_announcements: HashMap<SubscriptionKey, HashSet<String>> = HashMap::new();
+
+    loop {
+        start_task(CLASS_SUBSCRIPTION_TASK).await;
+        let now = Local::now();
+        let subscriptions = read_records::<ClassSubscription>(CLASS_SUBSCRIPTION_FILE).await;
+
+        for subscription in subscriptions {
+            let binusmaya_api = match subscription.source_api().await {
+                Some(binusmaya_api) => binusmaya_api,
+                None => continue,
+            };
+
+            let key = subscription.key();
+            // a restart or a new subscription only posts the sessions whose notice became due in the last check interval
+            let since = last_checked.get(&key).copied().unwrap_or_else(|| now - Duration::minutes(CHECK_INTERVAL));
+            let checked_until = post_due_sessions(ctx, &subscription, &binusmaya_api, &mut session_cache, since, now).await;
+            last_checked.insert(key.clone(), checked_until);
+
+            match post_new_announcements(ctx, &subscription, &binusmaya_api, seen_announcements.get(&key)).await {
+                Ok(announcement_ids) => {
+                    seen_announcements.insert(key, announcement_ids);
+                }
+                Err(e) => eprintln!("Failed to get the announcements of class {}: {:?}", subscription.class_id, e),
+            }
+        }
+
+        finish_task(CLASS_SUBSCRIPTION_TASK).await;
+        tokio::time::sleep(Duration::minutes(CHECK_INTERVAL).to_std().unwrap()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_notice_due_test() {
+        let now = Local.from_local_datetime(&NaiveDateTime::parse_from_str("2022-01-05T07:20:00", "%FT%X").unwrap()).unwrap();
+        let last_checked = now - Duration::minutes(CHECK_INTERVAL);
+
+        assert!(is_notice_due(now + Duration::minutes(NOTICE_BEFORE_START), last_checked, now));
+        assert!(is_notice_due(now + Duration::minutes(NOTICE_BEFORE_START - 2), last_checked, now));
+        assert!(!is_notice_due(now + Duration::minutes(NOTICE_BEFORE_START - CHECK_INTERVAL), last_checked, now), "posted in the previous check");
+        assert!(!is_notice_due(now + Duration::minutes(NOTICE_BEFORE_START + 1), last_checked, now), "posted in the next check");
+
+        let failed_start = now + Duration::minutes(NOTICE_BEFORE_START - 1);
+        let checked_until = notice_at(failed_start) - Duration::seconds(1);
+        assert!(is_notice_due(failed_start, checked_until, now + Duration::minutes(CHECK_INTERVAL)), "retried in the next check");
+    }
+
+    #[test]
+    fn mentions_course_test() {
+        let subscription = ClassSubscription {
+            guild_id: 1,
+            channel_id: 2,
+            class_id: "class".to_string(),
+            course_name: "Algorithm and Programming".to_string(),
+            class_code: "LA01".to_string(),
+            course_code: "COMP6047".to_string(),
+            source_member_id: 3,
+            source_account: "default".to_string(),
+        };
+
+        assert!(subscription.mentions_course("Quiz COMP6047 moved to next week"));
+        assert!(subscription.mentions_course("ALGORITHM AND PROGRAMMING final project"));
+        assert!(!subscription.mentions_course("LA01 Character Building midterm"));
+        assert!(!ClassSubscription { course_code: String::new(), ..subscription }.mentions_course("Scholarship COMP"));
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use serenity::{model::prelude::*, prelude::*};
+
+use crate::{
+    api::new_binusmaya_api::Class,
+    consts::{CLASS_ROLE_FILE, CLASS_SYNC_LOCK, COURSE_CHANNEL_FILE, GUILD_CONFIG, NEWBINUSMAYA_USER_DATA},
+    discord::{helper::{active_new_binusmaya_api, overwrite_records, read_records}, scheduler::{finish_task, start_task, CLASS_SYNC_TASK}},
+};
+
+/// A role created by the bot for a class code
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ClassRoleRecord {
+    pub guild_id: u64,
+    pub class_code: String,
+    pub role_id: u64,
+}
+
+/// A text channel created by the bot for a course
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CourseChannelRecord {
+    pub guild_id: u64,
+    pub course_code: String,
+    pub channel_id: u64,
+}
+
+/// Turns a course into a valid text channel name, e.g. `char6013-character-building-pancasila`
+pub fn course_channel_name(course_code: &str, course_name: &str) -> String {
+    format!("{} {}", course_code, course_name)
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+async fn registered_members(ctx: &Context, guild_id: GuildId) -> Vec<(Member, Vec<Class>)> {
+    let member_ids: HashSet<u64> = NEWBINUSMAYA_USER_DATA
+        .lock()
+        .await
+        .keys()
+        .map(|(member_id, _)| *member_id)
+        .collect();
+    let mut member_classes = Vec::new();
+
+    for member_id in member_ids {
+        let member = match guild_id.member(ctx, member_id).await {
+            Ok(member) => member,
+            Err(_) => continue,
+        };
+
+        // members with an expired token keep their roles until they re-register
+        let binusmaya_api = match active_new_binusmaya_api(&member_id).await {
+            Some(binusmaya_api) => binusmaya_api,
+            None => continue,
+        };
+
+        match binusmaya_api.get_classes().await {
+            Ok(classes) => member_classes.push((member, classes.list)),
+            Err(e) => eprintln!("Failed to get the classes of {}: {:?}", member_id, e),
+        }
+    }
+
+    member_classes
+}
+
+async fn sync_course_channels(ctx: &Context, guild_id: GuildId, courses: HashMap<String, String>) -> serenity::Result<()> {
+    let guild_channels = guild_id.channels(&ctx.http).await?;
+    let mut channel_records = read_records::<CourseChannelRecord>(COURSE_CHANNEL_FILE).await;
+
+    // forget the channels that were deleted by the server's admins
+    channel_records.retain(|record| {
+        record.guild_id != *guild_id.as_u64() || guild_channels.contains_key(&ChannelId(record.channel_id))
+    });
+
+    for (course_code, course_name) in courses {
+        let has_channel = channel_records
+            .iter()
+            .any(|record| record.guild_id == *guild_id.as_u64() && record.course_code.eq(&course_code));
+
+        if !has_channel {
+            let channel = guild_id
+                .create_channel(&ctx.http, |c| c
+                    .name(course_channel_name(&course_code, &course_name))
+                    .kind(ChannelType::Text)
+                    .topic(&course_name)
+                )
+                .await;
+
+            match channel {
+                Ok(channel) => channel_records.push(CourseChannelRecord {
+                    guild_id: *guild_id.as_u64(),
+                    course_code,
+                    channel_id: *channel.id.as_u64(),
+                }),
+                Err(e) => eprintln!("Failed to create the channel of {} in {}: {:?}", course_code, guild_id, e),
+            }
+        }
+    }
+
+    if let Err(e) = overwrite_records(COURSE_CHANNEL_FILE, &channel_records).await {
+        eprintln!("Failed to store the course channels of {}: {:?}", guild_id, e);
+    }
+
+    Ok(())
+}
+
+/// Gives every registered member of the guild a role for each of their class codes and removes the roles of classes they no longer take
+pub async fn sync_guild_classes(ctx: &Context, guild_id: GuildId) -> serenity::Result<()> {
+    let _lock = CLASS_SYNC_LOCK.lock().await;
+
+    let guild_config = match GUILD_CONFIG.lock().await.get(guild_id.as_u64()) {
+        Some(guild_config) if guild_config.class_roles.unwrap_or(false) => guild_config.clone(),
+        _ => return Ok(()),
+    };
+
+    let member_classes = registered_members(ctx, guild_id).await;
+    let guild_roles = guild_id.roles(&ctx.http).await?;
+    let mut role_records = read_records::<ClassRoleRecord>(CLASS_ROLE_FILE).await;
+
+    // forget the roles that were deleted by the server's admins
+    role_records.retain(|record| {
+        record.guild_id != *guild_id.as_u64() || guild_roles.contains_key(&RoleId(record.role_id))
+    });
+
+    let mut class_roles: HashMap<String, RoleId> = role_records
+        .iter()
+        .filter(|record| record.guild_id == *guild_id.as_u64())
+        .map(|record| (record.class_code.clone(), RoleId(record.role_id)))
+        .collect();
+    let mut courses: HashMap<String, String> = HashMap::new();
+
+    for class in member_classes.iter().flat_map(|(_, classes)| classes) {
+        courses.insert(class.course_code.clone(), class.course_name.clone());
+
+        if !class_roles.contains_key(&class.class_Code) {
+            match guild_id.create_role(&ctx.http, |r| r.name(&class.class_Code).mentionable(true)).await {
+                Ok(role) => {
+                    class_roles.insert(class.class_Code.clone(), role.id);
+                    role_records.push(ClassRoleRecord {
+                        guild_id: *guild_id.as_u64(),
+                        class_code: class.class_Code.clone(),
+                        role_id: *role.id.as_u64(),
+                    });
+                }
+                Err(e) => eprintln!("Failed to create the role of {} in {}: {:?}", class.class_Code, guild_id, e),
+            }
+        }
+    }
+
+    if let Err(e) = overwrite_records(CLASS_ROLE_FILE, &role_records).await {
+        eprintln!("Failed to store the class roles of {}: {:?}", guild_id, e);
+    }
+
+    for (mut member, classes) in member_classes {
+        let class_codes: HashSet<&str> = classes.iter().map(|class| class.class_Code.as_str()).collect();
+
+        for (class_code, role_id) in class_roles.iter() {
+            let has_role = member.roles.contains(role_id);
+            let in_class = class_codes.contains(class_code.as_str());
+
+            // e.g. a role above the bot's role can't be given, the other roles and members are still synced
+            if in_class && !has_role {
+                if let Err(e) = member.add_role(&ctx.http, *role_id).await {
+                    eprintln!("Failed to give the role of {} to {} in {}: {:?}", class_code, member.user.id, guild_id, e);
+                }
+            } else if !in_class && has_role {
+                if let Err(e) = member.remove_role(&ctx.http, *role_id).await {
+                    eprintln!("Failed to remove the role of {} from {} in {}: {:?}", class_code, member.user.id, guild_id, e);
+                }
+            }
+        }
+    }
+
+    if guild_config.course_channels.unwrap_or(false) {
+        sync_course_channels(ctx, guild_id, courses).await?;
+    }
+
+    Ok(())
+}
+
+/// Syncs the class roles of every guild that turned them on every 6 hours
+pub async fn class_sync_event(ctx: &Context) {
+    loop {
+        start_task(CLASS_SYNC_TASK).await;
+        let guild_ids: Vec<u64> = GUILD_CONFIG
+            .lock()
+            .await
+            .values()
+            .filter(|guild_config| guild_config.class_roles.unwrap_or(false))
+            .map(|guild_config| guild_config.guild_id)
+            .collect();
+
+        for guild_id in guild_ids {
+            if let Err(e) = sync_guild_classes(ctx, GuildId(guild_id)).await {
+                eprintln!("Failed to sync the class roles of {}: {:?}", guild_id, e);
+            }
+        }
+
+        finish_task(CLASS_SYNC_TASK).await;
+        tokio::time::sleep(Duration::hours(6).to_std().unwrap()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn course_channel_name_test() {
+        assert_eq!(course_channel_name("COMP6047", "Algorithm & Programming"), "comp6047-algorithm-programming");
+        assert_eq!(course_channel_name("CHAR6013", "Character Building: Pancasila"), "char6013-character-building-pancasila");
+    }
+}
@@ -0,0 +1,130 @@
+use serenity::{
+    builder::CreateActionRow,
+    framework::standard::{macros::command, Args, CommandResult},
+    model::{interactions::message_component::ButtonStyle, prelude::*},
+    prelude::*,
+};
+use std::collections::BTreeSet;
+
+use crate::{
+    consts::{NEWBINUSMAYA_USER_DATA, OLDBINUSMAYA_USER_DATA, PRIMARY_COLOR},
+    discord::helper::{await_invoker_interaction, language},
+};
+
+const SEND_BROADCAST: &str = "send_broadcast";
+const CANCEL_BROADCAST: &str = "cancel_broadcast";
+
+fn confirm_action_row(user_count: usize) -> CreateActionRow {
+    let mut ar = CreateActionRow::default();
+    ar.create_button(|b| b
+        .custom_id(SEND_BROADCAST)
+        .label(format!("Send to {} user(s)", user_count))
+        .style(ButtonStyle::Danger)
+    );
+    ar.create_button(|b| b
+        .custom_id(CANCEL_BROADCAST)
+        .label("Cancel")
+        .style(ButtonStyle::Secondary)
+    );
+
+    ar
+}
+
+/// Every user with a new or old binusmaya account, once per user
+async fn registered_users() -> BTreeSet<u64> {
+    let mut user_ids: BTreeSet<u64> = NEWBINUSMAYA_USER_DATA.lock().await.keys().map(|(member_id, _)| *member_id).collect();
+    user_ids.extend(OLDBINUSMAYA_USER_DATA.lock().await.keys().map(|(member_id, _)| *member_id));
+
+    user_ids
+}
+
+#[command]
+#[description("Send a maintenance notice to every registered user in DM, the notice is previewed before it's sent")]
+#[usage("[notice]")]
+#[example("The bot will be offline tonight from 22:00 to 23:00 WIB for maintenance")]
+pub async fn broadcast(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let notice = args.rest().trim().to_string();
+    if notice.is_empty() {
+        msg.channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| e
+                .colour(PRIMARY_COLOR)
+                .field("Missing notice", "Please enter the notice, e.g. `=admin broadcast The bot will be offline tonight`", false)
+            )
+        }).await?;
+
+        return Ok(());
+    }
+
+    let user_ids = registered_users().await;
+
+    let mut message = msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title("Maintenance Notice Preview")
+            .colour(PRIMARY_COLOR)
+            .description(&notice)
+            .footer(|f| f.text(format!("{} registered user(s) will receive this notice", user_ids.len())))
+        );
+        m.components(|c| c.add_action_row(confirm_action_row(user_ids.len())))
+    }).await?;
+
+    let mci = match await_invoker_interaction(ctx, msg, &mut message).await? {
+        Some(mci) => mci,
+        None => return Ok(()),
+    };
+
+    if mci.data.custom_id == CANCEL_BROADCAST {
+        mci.create_interaction_response(&ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage);
+            r.interaction_response_data(|d| {
+                d.create_embed(|e| e.colour(PRIMARY_COLOR).title("Broadcast Cancelled").description(&notice));
+                d.components(|c| c)
+            })
+        }).await?;
+
+        return Ok(());
+    }
+
+    mci.create_interaction_response(&ctx, |r| {
+        r.kind(InteractionResponseType::UpdateMessage);
+        r.interaction_response_data(|d| {
+            d.create_embed(|e| e
+                .colour(PRIMARY_COLOR)
+                .title("Sending Maintenance Notice")
+                .description(&notice)
+                .footer(|f| f.text(format!("Sending to {} user(s)", user_ids.len())))
+            );
+            d.components(|c| c)
+        })
+    }).await?;
+
+    let mut failed = 0;
+    for user_id in user_ids.iter() {
+        let language = language(user_id, None).await;
+        let sent = match UserId(*user_id).create_dm_channel(&ctx.http).await {
+            Ok(channel) => channel.id.send_message(&ctx.http, |m| {
+                m.embed(|e| e
+                    .colour(PRIMARY_COLOR)
+                    .title(tr!(language, "admin.maintenance"))
+                    .description(&notice)
+                )
+            }).await.is_ok(),
+            Err(_) => false,
+        };
+
+        if !sent {
+            eprintln!("Failed to send the maintenance notice to {}", user_id);
+            failed += 1;
+        }
+    }
+
+    message.edit(&ctx, |m| {
+        m.embed(|e| e
+            .colour(PRIMARY_COLOR)
+            .title("Maintenance Notice Sent")
+            .description(&notice)
+            .footer(|f| f.text(format!("Sent to {} user(s), {} failed", user_ids.len() - failed, failed)))
+        )
+    }).await?;
+
+    Ok(())
+}
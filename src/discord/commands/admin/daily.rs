@@ -0,0 +1,25 @@
+use serenity::{
+    framework::standard::{macros::command, CommandResult},
+    model::prelude::*,
+    prelude::*,
+};
+use std::time::Instant;
+
+use crate::discord::{discord::run_daily_task, helper::reply, scheduler::{is_task_running, DAILY_TASK}};
+
+#[command]
+#[description("Update today's student progress and post the forum reminders now instead of waiting for midnight")]
+pub async fn daily(ctx: &Context, msg: &Message) -> CommandResult {
+    if is_task_running(DAILY_TASK).await {
+        return reply(ctx, msg, "Already running", "The daily progress update is already running, use `=admin tasks` to see when it started").await;
+    }
+
+    reply(ctx, msg, "Daily progress update started", "Updating the progress of every new binusmaya account").await?;
+
+    let start = Instant::now();
+    if !run_daily_task(ctx).await {
+        return reply(ctx, msg, "Already running", "The daily progress update started in the meantime, use `=admin tasks` to see when it started").await;
+    }
+
+    reply(ctx, msg, "Daily progress update finished", format!("Finished in {:.1}s", start.elapsed().as_secs_f32())).await
+}
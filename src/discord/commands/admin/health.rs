@@ -0,0 +1,62 @@
+use serenity::{
+    framework::standard::{macros::command, CommandResult},
+    model::prelude::*,
+    prelude::*,
+};
+use std::time::Duration;
+
+use crate::{
+    consts::{CHROME_SERVER_URL, PRIMARY_COLOR},
+    third_party::BROWSERMOB_PROXY,
+};
+
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn chromedriver_status() -> String {
+    let client = reqwest::Client::new();
+    let status = client
+        .get(format!("{}/status", CHROME_SERVER_URL))
+        .timeout(HEALTH_CHECK_TIMEOUT)
+        .send()
+        .await;
+
+    let status: serde_json::Value = match status {
+        Ok(res) => match res.json().await {
+            Ok(status) => status,
+            Err(e) => return format!(":x: Invalid status: {}", e),
+        },
+        Err(e) => return format!(":x: Not reachable: {}", e),
+    };
+
+    match status["value"]["ready"].as_bool() {
+        Some(true) => ":white_check_mark: Ready".to_string(),
+        _ => format!(":warning: Not ready: {}", status["value"]["message"].as_str().unwrap_or("no message")),
+    }
+}
+
+async fn proxy_status() -> String {
+    match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, BROWSERMOB_PROXY.get_proxy()).await {
+        Ok(Ok(proxy)) => format!(":white_check_mark: Running with {} open prox(ies)", proxy.proxyList.len()),
+        Ok(Err(e)) => format!(":x: Not reachable: {}", e),
+        Err(_) => format!(":x: No response after {}s", HEALTH_CHECK_TIMEOUT.as_secs()),
+    }
+}
+
+#[command]
+#[description("Check whether chromedriver and browsermob proxy, which the headless logins need, are running")]
+pub async fn health(ctx: &Context, msg: &Message) -> CommandResult {
+    msg.channel_id.broadcast_typing(&ctx.http).await?;
+
+    let (chromedriver, proxy) = tokio::join!(chromedriver_status(), proxy_status());
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title("Third Party Apps")
+            .colour(PRIMARY_COLOR)
+            .field(format!("Chromedriver ({})", CHROME_SERVER_URL), chromedriver, false)
+            .field(format!("Browsermob Proxy ({}:{})", BROWSERMOB_PROXY.host, BROWSERMOB_PROXY.port), proxy, false)
+        )
+    }).await?;
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod broadcast;
+pub mod daily;
+pub mod health;
+pub mod refresh;
+pub mod reload;
+pub mod tasks;
+pub mod users;
@@ -0,0 +1,31 @@
+use serenity::{
+    framework::standard::{macros::command, Args, CommandResult},
+    model::prelude::*,
+    prelude::*,
+};
+
+use crate::discord::helper::{active_account, refresh_cookie, reply, CookieError};
+
+#[command]
+#[description("Log an old binusmaya account in again, the active account of the user is used without an account name")]
+#[usage("[user id] [account name]")]
+#[example("921712744749756427 staff")]
+pub async fn refresh(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let user_id = match args.single::<UserId>() {
+        Ok(user_id) => *user_id.as_u64(),
+        Err(_) => return reply(ctx, msg, "Missing user", "Please enter the id or mention of the user, e.g. `=admin refresh [user id]`").await,
+    };
+    let account = match args.single::<String>() {
+        Ok(account) => account,
+        Err(_) => active_account(&user_id).await,
+    };
+
+    msg.channel_id.broadcast_typing(&ctx.http).await?;
+
+    match refresh_cookie(&ctx.http, &(user_id, account.clone())).await {
+        Ok(_) => reply(ctx, msg, "Cookie refreshed", format!("<@{}>'s **{}** account is logged in to old binusmaya again", user_id, account)).await,
+        Err(CookieError::NotRegistered) => reply(ctx, msg, "Account not found", format!("<@{}> doesn't have an old binusmaya account named **{}**", user_id, account)).await,
+        Err(CookieError::LoginFailed) => reply(ctx, msg, "Login failed", format!("Couldn't log <@{}>'s **{}** account in with the stored credential, the user has to register again", user_id, account)).await,
+        Err(CookieError::Unavailable) => reply(ctx, msg, "Old binusmaya unavailable", "Old binusmaya couldn't be reached, the account wasn't marked as failed").await,
+    }
+}
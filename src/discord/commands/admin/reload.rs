@@ -0,0 +1,33 @@
+use serenity::{
+    framework::standard::{macros::command, CommandResult},
+    model::prelude::*,
+    prelude::*,
+};
+
+use crate::{
+    consts::{NEWBINUSMAYA_USER_DATA, OLDBINUSMAYA_USER_DATA},
+    discord::{discord::reload_user_data, helper::{reply, warm_up_cookies}},
+};
+
+#[command]
+#[description("Download the stored user data again and replace the user data in memory with it, e.g. after editing the files in dropbox")]
+pub async fn reload(ctx: &Context, msg: &Message) -> CommandResult {
+    msg.channel_id.broadcast_typing(&ctx.http).await?;
+
+    // the error isn't `Send`, so it's turned into its message before the reply is awaited
+    if let Err(e) = reload_user_data().await.map_err(|e| e.to_string()) {
+        eprintln!("Failed to reload user data: {}", e);
+        return reply(ctx, msg, "Reload failed", format!("Couldn't download the user data: {}", e)).await;
+    }
+
+    // the reloaded old binusmaya accounts aren't logged in yet
+    let http = ctx.http.clone();
+    tokio::spawn(async move {
+        warm_up_cookies(&http).await;
+    });
+
+    let new_binusmaya_accounts = NEWBINUSMAYA_USER_DATA.lock().await.len();
+    let old_binusmaya_accounts = OLDBINUSMAYA_USER_DATA.lock().await.len();
+
+    reply(ctx, msg, "User data reloaded", format!("Loaded **{}** new binusmaya and **{}** old binusmaya account(s)", new_binusmaya_accounts, old_binusmaya_accounts)).await
+}
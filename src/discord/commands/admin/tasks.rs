@@ -0,0 +1,26 @@
+use serenity::{
+    framework::standard::{macros::command, CommandResult},
+    model::prelude::*,
+    prelude::*,
+};
+
+use crate::{
+    consts::{PRIMARY_COLOR, TASK_STATUS},
+    discord::scheduler::describe_tasks,
+};
+
+#[command]
+#[description("Show whether each background task is running and when it last finished")]
+pub async fn tasks(ctx: &Context, msg: &Message) -> CommandResult {
+    let content = describe_tasks(&*TASK_STATUS.lock().await);
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title("Background Tasks")
+            .colour(PRIMARY_COLOR)
+            .description(content)
+        )
+    }).await?;
+
+    Ok(())
+}
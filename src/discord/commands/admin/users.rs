@@ -0,0 +1,84 @@
+use chrono::{DateTime, Duration, Local};
+use serenity::{
+    framework::standard::{macros::command, CommandResult},
+    model::prelude::*,
+    prelude::*,
+};
+use std::ops::Add;
+
+use crate::{
+    consts::{NEWBINUSMAYA_USER_DATA, OLDBINUSMAYA_USER_DATA},
+    discord::helper::{msg_language, Paginator},
+};
+
+/// When the token expires and how many days are left until then
+fn describe_token_expiry(jwt_exp: DateTime<Local>, now: DateTime<Local>) -> String {
+    if jwt_exp <= now {
+        return format!("expired on {}", jwt_exp.format("%F"));
+    }
+
+    format!("expires on {} ({} day(s) left)", jwt_exp.format("%F"), (jwt_exp - now).num_days())
+}
+
+#[command]
+#[description("List the registered accounts, new binusmaya accounts whose token expires first come first")]
+pub async fn users(ctx: &Context, msg: &Message) -> CommandResult {
+    let now = Local::now();
+
+    let mut new_binusmaya_accounts: Vec<(DateTime<Local>, String)> = NEWBINUSMAYA_USER_DATA
+        .lock()
+        .await
+        .iter()
+        .map(|((member_id, account), user_auth_info)| {
+            let jwt_exp = user_auth_info.last_registered.add(Duration::weeks(52));
+            let auto_refresh = match &user_auth_info.user_credential {
+                Some(_) if user_auth_info.refresh_failed => "auto refresh failed",
+                Some(_) => "auto refresh on",
+                None => "auto refresh off",
+            };
+
+            (jwt_exp, format!("<@{}> **{}** - {}, {}\n", member_id, account, describe_token_expiry(jwt_exp, now), auto_refresh))
+        })
+        .collect();
+    new_binusmaya_accounts.sort();
+
+    let mut old_binusmaya_accounts: Vec<String> = OLDBINUSMAYA_USER_DATA
+        .lock()
+        .await
+        .iter()
+        .map(|((member_id, account), user_auth_info)| {
+            let status = match &user_auth_info.cookie {
+                _ if user_auth_info.login_failed => "login failed",
+                Some(_) => "logged in",
+                None => "not logged in",
+            };
+
+            format!("<@{}> **{}** - {}, {}\n", member_id, account, user_auth_info.binusian_data.display_name, status)
+        })
+        .collect();
+    old_binusmaya_accounts.sort();
+
+    let mut content = format!("__New Binusmaya ({})__\n", new_binusmaya_accounts.len());
+    content.extend(new_binusmaya_accounts.into_iter().map(|(_, line)| line));
+    content.push_str(&format!("\n__Old Binusmaya ({})__\n", old_binusmaya_accounts.len()));
+    content.extend(old_binusmaya_accounts);
+
+    Paginator::new(content, msg_language(msg).await).send(ctx, msg, "Registered Users").await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDateTime, TimeZone};
+
+    #[test]
+    fn describe_token_expiry_test() {
+        let now = Local.from_local_datetime(&NaiveDateTime::parse_from_str("2022-01-05T07:20:00", "%FT%X").unwrap()).unwrap();
+
+        assert_eq!(describe_token_expiry(now + Duration::days(3), now), "expires on 2022-01-08 (3 day(s) left)");
+        assert_eq!(describe_token_expiry(now + Duration::hours(5), now), "expires on 2022-01-05 (0 day(s) left)");
+        assert_eq!(describe_token_expiry(now - Duration::days(1), now), "expired on 2022-01-04");
+    }
+}
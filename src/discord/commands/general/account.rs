@@ -0,0 +1,141 @@
+use chrono::Duration;
+use serenity::{
+    framework::standard::{macros::command, Args, CommandResult},
+    model::prelude::*,
+    prelude::*,
+    utils::MessageBuilder,
+};
+use std::ops::Add;
+
+use crate::{
+    consts::{ACTIVE_ACCOUNT, ACTIVE_ACCOUNT_FILE, DEFAULT_ACCOUNT, NEWBINUSMAYA_USER_DATA, OLDBINUSMAYA_USER_DATA, PRIMARY_COLOR},
+    discord::{discord::ActiveAccountRecord, helper::{guild_prefix, msg_language, overwrite_records, read_records}},
+};
+
+async fn list_accounts(ctx: &Context, msg: &Message) -> CommandResult {
+    let member_id = *msg.author.id.as_u64();
+    let language = msg_language(msg).await;
+    let prefix = guild_prefix(msg.guild_id).await;
+    let active_account = ACTIVE_ACCOUNT
+        .lock()
+        .await
+        .get(&member_id)
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_ACCOUNT.to_string());
+
+    let mut new_binusmaya_accounts: Vec<(String, String)> = NEWBINUSMAYA_USER_DATA
+        .lock()
+        .await
+        .iter()
+        .filter(|((id, _), _)| *id == member_id)
+        .map(|((_, account), user_auth_info)| {
+            let jwt_exp = user_auth_info.last_registered.add(Duration::weeks(52));
+            (account.clone(), tr!(language, "account.list.token_expiry", jwt_exp.format("%F")))
+        })
+        .collect();
+    new_binusmaya_accounts.sort();
+
+    let mut old_binusmaya_accounts: Vec<(String, String)> = OLDBINUSMAYA_USER_DATA
+        .lock()
+        .await
+        .iter()
+        .filter(|((id, _), _)| *id == member_id)
+        .map(|((_, account), user_auth_info)| (account.clone(), user_auth_info.binusian_data.display_name.clone()))
+        .collect();
+    old_binusmaya_accounts.sort();
+
+    let describe = |accounts: &Vec<(String, String)>| {
+        let mut content = MessageBuilder::new();
+        if accounts.is_empty() {
+            content.push(tr!(language, "account.list.none"));
+        }
+
+        for (account, info) in accounts {
+            content.push_bold(account);
+            if account.eq(&active_account) {
+                content.push(tr!(language, "account.list.active"));
+            }
+            content.push_line(format!(" - {}", info));
+        }
+
+        content.build()
+    };
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title(tr!(language, "account.list.title"))
+            .colour(PRIMARY_COLOR)
+            .field(tr!(language, "common.new_binusmaya"), describe(&new_binusmaya_accounts), false)
+            .field(tr!(language, "common.old_binusmaya"), describe(&old_binusmaya_accounts), false)
+            .footer(|f| f.text(tr!(language, "account.list.footer", prefix)))
+        )
+    }).await?;
+
+    Ok(())
+}
+
+async fn use_account(ctx: &Context, msg: &Message, account: String) -> CommandResult {
+    let account_key = (*msg.author.id.as_u64(), account.clone());
+    let language = msg_language(msg).await;
+    let is_registered = NEWBINUSMAYA_USER_DATA.lock().await.contains_key(&account_key)
+        || OLDBINUSMAYA_USER_DATA.lock().await.contains_key(&account_key);
+
+    if !is_registered {
+        let prefix = guild_prefix(msg.guild_id).await;
+        msg.channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| e
+                .colour(PRIMARY_COLOR)
+                .field(tr!(language, "account.not_found.title"), tr!(language, "account.not_found_any.body", account, prefix), false)
+            )
+        }).await?;
+
+        return Ok(());
+    }
+
+    ACTIVE_ACCOUNT.lock().await.insert(account_key.0, account.clone());
+
+    let mut active_account_records = read_records::<ActiveAccountRecord>(ACTIVE_ACCOUNT_FILE).await;
+    active_account_records.retain(|record| record.member_id != account_key.0);
+    active_account_records.push(ActiveAccountRecord {
+        member_id: account_key.0,
+        account: account.clone(),
+    });
+
+    if let Err(e) = overwrite_records(ACTIVE_ACCOUNT_FILE, &active_account_records).await {
+        eprintln!("Failed to store active account of {}: {:?}", account_key.0, e);
+    }
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .colour(PRIMARY_COLOR)
+            .field(tr!(language, "account.switched.title"), tr!(language, "account.switched.body", account), false)
+        )
+    }).await?;
+
+    Ok(())
+}
+
+#[command]
+#[description("List your registered BINUSMAYA accounts or switch the account used by BINUSMAYA commands")]
+#[usage("[list | use [account name]]")]
+#[example("use staff")]
+pub async fn account(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    match args.single::<String>().ok().as_deref() {
+        Some("use") => match args.single::<String>() {
+            Ok(account) => use_account(ctx, msg, account).await?,
+            Err(_) => {
+                let language = msg_language(msg).await;
+                let prefix = guild_prefix(msg.guild_id).await;
+                msg.channel_id.send_message(&ctx.http, |m| {
+                    m.embed(|e| e
+                        .colour(PRIMARY_COLOR)
+                        .field(tr!(language, "account.missing_name.title"), tr!(language, "account.missing_name.body", prefix), false)
+                    )
+                }).await?;
+            }
+        },
+        _ => list_accounts(ctx, msg).await?,
+    }
+
+    Ok(())
+}
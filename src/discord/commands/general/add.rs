@@ -1,9 +1,8 @@
 use chrono::{Duration, Local};
 use csv_async::AsyncWriterBuilder;
-use magic_crypt::MagicCryptTrait;
 use serenity::{
-    framework::standard::{macros::command, CommandResult},
-    model::prelude::*,
+    framework::standard::{macros::command, Args, CommandResult},
+    model::{prelude::*, interactions::message_component::ButtonStyle},
     prelude::*, builder::{CreateSelectMenuOption, CreateSelectMenu, CreateActionRow},
 };
 use std::{io::Write, str::FromStr, fmt};
@@ -12,10 +11,11 @@ use std::{error::Error, fs::OpenOptions};
 use thirtyfour::{error::WebDriverError, Capabilities, DesiredCapabilities, Proxy, WebDriver};
 
 use crate::{
-    consts::{CHROME_BINARY, NEW_BINUSMAYA, PRIMARY_COLOR, NEWBINUSMAYA_USER_DATA, NEWBINUSMAYA_USER_FILE, OLD_BINUSMAYA, OLDBINUSMAYA_USER_DATA, OLDBINUSMAYA_USER_FILE, CHROME_SERVER_URL, MAGIC_CRYPT},
-    discord::{discord::{NewBinusmayaUserAuthInfo, NewBinusmayaUserRecord, UserCredential, OldBinusmayaUserRecord, UserBinusianData}, helper::ParseError},
+    consts::{CHROME_BINARY, DEFAULT_ACCOUNT, NEW_BINUSMAYA, PRIMARY_COLOR, NEWBINUSMAYA_USER_DATA, NEWBINUSMAYA_USER_FILE, OLD_BINUSMAYA, OLDBINUSMAYA_USER_DATA, OLDBINUSMAYA_USER_FILE, CHROME_SERVER_URL, PASSWORD_CRYPT, TOKEN_CRYPT},
+    discord::{discord::{NewBinusmayaUserAuthInfo, NewBinusmayaUserRecord, UserCredential, OldBinusmayaUserAuthInfo, OldBinusmayaUserRecord, UserBinusianData}, helper::{msg_language, ParseError}},
     api::{dropbox_api, old_binusmaya_api::{OldBinusmayaAPI, BinusianData}},
-    third_party::{BrowserMobProxy, Selenium, Status},
+    locale::Language,
+    third_party::{BrowserMobProxy, Selenium, Status, BROWSERMOB_PROXY},
 };
 
 #[derive(Debug)]
@@ -59,10 +59,10 @@ impl Binusmaya {
         opt
     }
 
-    fn select_menu() -> CreateSelectMenu {
+    fn select_menu(language: Language) -> CreateSelectMenu {
         let mut menu = CreateSelectMenu::default();
         menu.custom_id("binusmaya_select");
-        menu.placeholder("Choose version of Binusmaya");
+        menu.placeholder(tr!(language, "add.choose_version.placeholder"));
         menu.options(|f| {
             f.add_option(Self::NewBinusmaya.menu_option());
             f.add_option(Self::OldBinusmaya.menu_option())
@@ -71,9 +71,9 @@ impl Binusmaya {
         menu
     }
 
-    fn action_row() -> CreateActionRow {
+    fn action_row(language: Language) -> CreateActionRow {
         let mut ar = CreateActionRow::default();
-        ar.add_select_menu(Self::select_menu());
+        ar.add_select_menu(Self::select_menu(language));
 
         ar
     }
@@ -81,10 +81,30 @@ impl Binusmaya {
 
 
 
+const STORE_CREDENTIAL: &str = "store_credential";
+const SKIP_CREDENTIAL: &str = "skip_credential";
+
+fn credential_action_row(language: Language) -> CreateActionRow {
+    let mut ar = CreateActionRow::default();
+    ar.create_button(|b| b
+        .custom_id(STORE_CREDENTIAL)
+        .label(tr!(language, "add.store_credential"))
+        .style(ButtonStyle::Primary)
+    );
+    ar.create_button(|b| b
+        .custom_id(SKIP_CREDENTIAL)
+        .label(tr!(language, "add.skip_credential"))
+        .style(ButtonStyle::Secondary)
+    );
+
+    ar
+}
+
 async fn launch_selenium(
     user_credential: &UserCredential,
     proxy: &BrowserMobProxy,
     binus_ver: String,
+    language: Language,
 ) -> Result<CookieOutput<Status<String>, Option<String>>, WebDriverError> {
     proxy.create_proxy().await?;
 
@@ -124,7 +144,7 @@ async fn launch_selenium(
 
     BrowserMobProxy::new_har(&proxy).await?;
     let is_valid = selenium.run(&binus_ver.to_string()).await.unwrap_or(
-		Status::ERROR("Error in registering, please try again. If the problem still persist, please contact `PlayerPlay#9549` or open a new issue [here](https://github.com/BryanEgbert/BINUSMAYA_Discord_Bot/issues)".to_string())
+		Status::ERROR(tr!(language, "add.error.body"))
 	);
 
     if let Status::VALID(_) = is_valid {
@@ -136,8 +156,9 @@ async fn launch_selenium(
                 if entry["request"]["url"].as_str().unwrap().eq("https://binusmaya.binus.ac.id/services/ci/index.php/general/getBinusianData") {
                     let binusian_data: BinusianData = serde_json::from_str(entry["response"]["content"]["text"].as_str().unwrap().clone()).unwrap();
                     let user_binusian_data = UserBinusianData::init_data(&binusian_data);
-                    let old_binusmaya_api = OldBinusmayaAPI::login(&user_binusian_data, user_credential).await;
-                    cookie = Some(old_binusmaya_api.cookie);
+                    cookie = OldBinusmayaAPI::login(&user_binusian_data, user_credential).await
+                        .map(|old_binusmaya_api| old_binusmaya_api.cookie)
+                        .ok();
                     break;
                 }
             }
@@ -158,32 +179,71 @@ async fn launch_selenium(
 
 }
 
+async fn get_bearer_token(proxy: &BrowserMobProxy) -> Result<String, reqwest::Error> {
+    let har = proxy.get_har().await?;
+    let len = har["log"]["entries"].as_array().unwrap().len();
+    let bearer_token =
+        &har["log"]["entries"][len - 1]["request"]["headers"][6]["value"].to_string();
+
+    Ok(bearer_token[1..bearer_token.len() - 1].to_string())
+}
+
+/// Logs in to new binusmaya again with a headless browser, returns `None` if the credential is no longer valid
+pub async fn refresh_new_binusmaya_token(user_credential: &UserCredential) -> Result<Option<String>, WebDriverError> {
+    let proxy = BROWSERMOB_PROXY;
+
+    let output = launch_selenium(user_credential, &proxy, NEW_BINUSMAYA.to_string(), Language::default()).await;
+    let token = match output {
+        Ok(CookieOutput::Out(Status::VALID(_), _)) => Some(get_bearer_token(&proxy).await?),
+        Ok(_) => None,
+        Err(e) => {
+            proxy.delete_proxy().await?;
+            return Err(e);
+        }
+    };
+
+    proxy.delete_proxy().await?;
+
+    Ok(token)
+}
+
 async fn write_user_data(
     binus_ver: &Binusmaya,
     proxy: &BrowserMobProxy,
     msg: &Message,
     user_credential: &UserCredential,
+    account: &str,
+    store_credential: bool,
     cookie: Option<String>
 ) -> Result<(), Box<dyn Error>> {
     match binus_ver {
         Binusmaya::NewBinusmaya => {
-            let har = proxy.get_har().await?;
-            let len = har["log"]["entries"].as_array().unwrap().len();
-            let bearer_token =
-                &har["log"]["entries"][len - 1]["request"]["headers"][6]["value"].to_string();
-                
+            let auth = get_bearer_token(proxy).await?;
+            let encrypted_user_credential = if store_credential {
+                Some(UserCredential {
+                    email: user_credential.email.clone(),
+                    password: PASSWORD_CRYPT.encrypt(&user_credential.password)
+                })
+            } else {
+                None
+            };
+
             let user_record = &NewBinusmayaUserRecord {
                 member_id: *msg.author.id.as_u64(),
-                auth: bearer_token[1..bearer_token.len() - 1].to_string(),
+                auth: TOKEN_CRYPT.encrypt(&auth),
                 last_registered: Local::now(),
+                user_credential: encrypted_user_credential,
+                account: Some(account.to_string()),
             };
                 
             let user_data = NEWBINUSMAYA_USER_DATA.clone();
             user_data.lock().await.insert(
-                user_record.member_id,
+                (user_record.member_id, account.to_string()),
                 NewBinusmayaUserAuthInfo {
-                    auth: user_record.auth.clone(),
+                    auth,
                     last_registered: user_record.last_registered,
+                    user_credential: user_record.user_credential.clone(),
+                    refresh_failed: false,
                 },
             );
         
@@ -209,26 +269,35 @@ async fn write_user_data(
             dropbox_api::upload_file(NEWBINUSMAYA_USER_FILE.to_string()).await?;
         },
         Binusmaya::OldBinusmaya => {
-            let cookie_clone = cookie.clone();
+            let cookie = cookie.ok_or("Failed to log in to old binusmaya")?;
             let old_binusmaya_api = OldBinusmayaAPI {
-                cookie: cookie_clone.unwrap()
+                cookie: cookie.clone()
             };
             let binusian_data = old_binusmaya_api.get_binusian_data().await?;
             let user_binusian_data = UserBinusianData::init_data(&binusian_data);
 
             let encrypted_user_credential = UserCredential {
                 email: user_credential.email.clone(),
-                password: MAGIC_CRYPT.encrypt_str_to_base64(user_credential.password.clone())
+                password: PASSWORD_CRYPT.encrypt(&user_credential.password)
             };
 
             let user_record = OldBinusmayaUserRecord {
                 member_id: *msg.author.id.as_u64(),
                 user_credential: encrypted_user_credential,
-                binusian_data: user_binusian_data
+                binusian_data: user_binusian_data,
+                account: Some(account.to_string()),
             };
 
             let user_data = OLDBINUSMAYA_USER_DATA.clone();
-            user_data.lock().await.insert(*msg.author.id.as_u64(), cookie.unwrap());
+            user_data.lock().await.insert(
+                (user_record.member_id, account.to_string()),
+                OldBinusmayaUserAuthInfo {
+                    cookie: Some(cookie),
+                    user_credential: user_record.user_credential.clone(),
+                    binusian_data: user_record.binusian_data.clone(),
+                    login_failed: false,
+                },
+            );
 
             let mut wtr = AsyncWriterBuilder::new()
                 .has_headers(false)
@@ -256,21 +325,21 @@ async fn write_user_data(
 async fn add_account(
     user_credential: UserCredential,
     binus_ver: Binusmaya,
+    account: String,
+    store_credential: bool,
     msg: &Message,
     ctx: &Context,
 ) -> CommandResult {
-    let proxy = BrowserMobProxy {
-        host: "localhost",
-        port: 8082,
-    };
+    let proxy = BROWSERMOB_PROXY;
+    let language = msg_language(msg).await;
 
     let binusmaya_ver = binus_ver.clone();
     let user_credential_clone = user_credential.clone();
 
     let handle = tokio::task::spawn(async move {
         match binusmaya_ver {
-            Binusmaya::NewBinusmaya => launch_selenium(&user_credential_clone, &proxy, NEW_BINUSMAYA.to_string()).await.unwrap(),
-            Binusmaya::OldBinusmaya =>  launch_selenium(&user_credential_clone.clone(), &proxy, OLD_BINUSMAYA.to_string()).await.unwrap()
+            Binusmaya::NewBinusmaya => launch_selenium(&user_credential_clone, &proxy, NEW_BINUSMAYA.to_string(), language).await.unwrap(),
+            Binusmaya::OldBinusmaya =>  launch_selenium(&user_credential_clone.clone(), &proxy, OLD_BINUSMAYA.to_string(), language).await.unwrap()
         }
     })
     .await?;
@@ -279,25 +348,25 @@ async fn add_account(
         CookieOutput::Out(Status::VALID(output), cookie) => {
             match binus_ver {
                 Binusmaya::NewBinusmaya => {
-                    write_user_data(&binus_ver, &proxy, &msg, &user_credential, cookie).await.unwrap();
+                    write_user_data(&binus_ver, &proxy, msg, &user_credential, &account, store_credential, cookie).await.unwrap();
     
                     msg.author
                         .dm(&ctx, |m| {
                             m.embed(|e| {
                                 e.colour(PRIMARY_COLOR)
-                                    .field("Account Registered", output, false)
+                                    .field(tr!(language, "add.registered"), output, false)
                             })
                         })
                         .await?;
                 },
                 Binusmaya::OldBinusmaya => {
-                    write_user_data(&binus_ver, &proxy, &msg, &user_credential, cookie).await.unwrap();
+                    write_user_data(&binus_ver, &proxy, msg, &user_credential, &account, store_credential, cookie).await.unwrap();
 
                     msg.author
                         .dm(&ctx, |m| {
                             m.embed(|e| {
                                 e.colour(PRIMARY_COLOR)
-                                    .field("Account Registered", output, false)
+                                    .field(tr!(language, "add.registered"), output, false)
                             })
                         })
                         .await?;
@@ -309,7 +378,7 @@ async fn add_account(
                 .dm(&ctx, |m| {
                     m.embed(|e| {
                         e.colour(PRIMARY_COLOR)
-                            .field("Account is not valid", output, false)
+                            .field(tr!(language, "add.invalid"), output, false)
                     })
                 })
                 .await?;
@@ -319,7 +388,7 @@ async fn add_account(
                 .dm(&ctx, |m| {
                     m.embed(|e| {
                         e.colour(PRIMARY_COLOR)
-                            .field("Error in registering", output, false)
+                            .field(tr!(language, "add.error.title"), output, false)
                     })
                 })
                 .await?;
@@ -334,32 +403,71 @@ async fn add_account(
 #[command]
 #[only_in("dm")]
 #[description("Add BINUS account to discord bot")]
-#[usage("[email];[password]")]
-async fn add(ctx: &Context, msg: &Message) -> CommandResult {
+#[usage("[account name]")]
+#[example("staff")]
+async fn add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     msg.react(&ctx, '👍').await?;
+    let language = msg_language(msg).await;
+
+    let account = args.single::<String>().unwrap_or_else(|_| DEFAULT_ACCOUNT.to_string());
+    if !account.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        msg.channel_id.send_message(&ctx, |m| {
+            m.embed(|e| e
+                .colour(PRIMARY_COLOR)
+                .field(tr!(language, "add.invalid_name.title"), tr!(language, "add.invalid_name.body"), false)
+            )
+        }).await?;
+
+        return Ok(());
+    }
 
     let binus_ver = msg.channel_id.send_message(&ctx, |m| {
         m.embed(|e| e
             .colour(PRIMARY_COLOR)
-            .field("Choose Binusmaya Version", "Please choose Binusmaya version you want to register to.", false)
+            .field(tr!(language, "add.choose_version.title"), tr!(language, "add.choose_version.body"), false)
         );
-        m.components(|c| c.add_action_row(Binusmaya::action_row()))
+        m.components(|c| c.add_action_row(Binusmaya::action_row(language)))
     }).await?;
 
     let mci = match binus_ver.await_component_interaction(&ctx).timeout(Duration::minutes(1).to_std().unwrap()).await {
         Some(ci) => ci,
         None => {
-            msg.reply(&ctx, "Timed Out, please try again").await?;
+            msg.reply(&ctx, tr!(language, "add.timed_out")).await?;
             return Ok(());
         }
     };
 
     let binusmaya_version = Binusmaya::from_str(&mci.data.values.get(0).unwrap()).unwrap();
 
+    let mci = match binusmaya_version {
+        Binusmaya::NewBinusmaya => {
+            mci.create_interaction_response(&ctx, |r| {
+                r.kind(InteractionResponseType::UpdateMessage);
+                r.interaction_response_data(|d| {
+                    d.create_embed(|e| e
+                        .colour(PRIMARY_COLOR)
+                        .field(tr!(language, "add.auto_refresh.title"), tr!(language, "add.auto_refresh.body"), false)
+                    );
+                    d.components(|c| c.add_action_row(credential_action_row(language)))
+                })
+            }).await?;
+
+            match binus_ver.await_component_interaction(&ctx).timeout(Duration::minutes(1).to_std().unwrap()).await {
+                Some(ci) => ci,
+                None => {
+                    msg.reply(&ctx, tr!(language, "add.timed_out")).await?;
+                    return Ok(());
+                }
+            }
+        },
+        Binusmaya::OldBinusmaya => mci,
+    };
+    let store_credential = mci.data.custom_id.eq(STORE_CREDENTIAL);
+
     mci.create_interaction_response(&ctx, |r| {
         r.kind(InteractionResponseType::ChannelMessageWithSource);
         r.interaction_response_data(|d| 
-            d.content("Please enter your Binus email and password with format of `[email] [password]`")
+            d.content(tr!(language, "add.enter_credential"))
         )
     }).await?;
 
@@ -373,11 +481,12 @@ async fn add(ctx: &Context, msg: &Message) -> CommandResult {
 
             match binusmaya_version {
                 Binusmaya::NewBinusmaya=> {
-                    if NEWBINUSMAYA_USER_DATA.lock().await.contains_key(msg.author.id.as_u64()) {
+                    let account_key = (*msg.author.id.as_u64(), account.clone());
+                    if NEWBINUSMAYA_USER_DATA.lock().await.contains_key(&account_key) {
                         let jwt_exp = NEWBINUSMAYA_USER_DATA
                             .lock()
                             .await
-                            .get(msg.author.id.as_u64())
+                            .get(&account_key)
                             .unwrap()
                             .last_registered
                             .add(Duration::weeks(52));
@@ -387,15 +496,15 @@ async fn add(ctx: &Context, msg: &Message) -> CommandResult {
                                 .send_message(&ctx, |m| {
                                     m.embed(|e| {
                                         e.colour(PRIMARY_COLOR).field(
-                                            "Registering...",
-                                            "Please wait a few seconds",
+                                            tr!(language, "add.registering.title"),
+                                            tr!(language, "add.registering.body"),
                                             false,
                                         )
                                     })
                                 })
                                 .await?;
 
-                            add_account(user_credential, binusmaya_version, msg, ctx).await.unwrap();
+                            add_account(user_credential, binusmaya_version, account, store_credential, msg, ctx).await.unwrap();
 
 
                         } else {
@@ -403,10 +512,12 @@ async fn add(ctx: &Context, msg: &Message) -> CommandResult {
                                 .send_message(&ctx, |m| {
                                     m.embed(|e| {
                                         e.colour(PRIMARY_COLOR).field(
-                                            "You've already registered",
-                                            format!(
-                                                "Please wait **{} days** to re-register your account",
-                                                jwt_exp.signed_duration_since(now).num_days()
+                                            tr!(language, "add.already_registered.title"),
+                                            tr!(
+                                                language,
+                                                "add.already_registered.body",
+                                                jwt_exp.signed_duration_since(now).num_days(),
+                                                account
                                             ),
                                             false,
                                         )
@@ -419,8 +530,8 @@ async fn add(ctx: &Context, msg: &Message) -> CommandResult {
                             .send_message(&ctx, |m| {
                                 m.embed(|e| {
                                     e.colour(PRIMARY_COLOR).field(
-                                        "Registering...",
-                                        "Please wait a few seconds",
+                                        tr!(language, "add.registering.title"),
+                                        tr!(language, "add.registering.body"),
                                         false,
                                     )
                                 })
@@ -428,7 +539,7 @@ async fn add(ctx: &Context, msg: &Message) -> CommandResult {
                         )
                         .await?;
 
-                        add_account(user_credential, binusmaya_version, msg, ctx).await.unwrap();
+                        add_account(user_credential, binusmaya_version, account, store_credential, msg, ctx).await.unwrap();
                     }
 
                 },
@@ -437,15 +548,15 @@ async fn add(ctx: &Context, msg: &Message) -> CommandResult {
                         .send_message(&ctx, |m| {
                             m.embed(|e| {
                                 e.colour(PRIMARY_COLOR).field(
-                                    "Registering...",
-                                    "Please wait a few seconds",
+                                    tr!(language, "add.registering.title"),
+                                    tr!(language, "add.registering.body"),
                                     false,
                                 )
                             })
                         }
                     ).await?;
 
-                    add_account(user_credential, binusmaya_version, msg, ctx).await.unwrap();            
+                    add_account(user_credential, binusmaya_version, account, store_credential, msg, ctx).await.unwrap();            
                 }
             }
 
@@ -453,7 +564,7 @@ async fn add(ctx: &Context, msg: &Message) -> CommandResult {
             msg.channel_id.send_message(&ctx, |m| {
                 m.embed(|e| e
                     .colour(PRIMARY_COLOR)
-                    .field("Error", "Missing email or password", false)
+                    .field(tr!(language, "add.missing_credential.title"), tr!(language, "add.missing_credential.body"), false)
                 )
             }).await?;
 
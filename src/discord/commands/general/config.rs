@@ -0,0 +1,182 @@
+use serenity::{
+    framework::standard::{macros::command, Args, CommandResult, Delimiter},
+    model::prelude::*,
+    prelude::*,
+    utils::MessageBuilder,
+};
+
+use crate::{
+    consts::{DEFAULT_PREFIX, GUILD_CONFIG, PRIMARY_COLOR},
+    discord::{class_sync::sync_guild_classes, discord::{GuildConfig, COMMAND_GROUPS}, helper::{msg_language, reply, save_guild_config, DEFAULT_INTERACTION_TIMEOUT, MAX_INTERACTION_TIMEOUT}},
+    locale::Language,
+};
+
+fn on_off(value: Option<bool>, language: Language) -> String {
+    if value.unwrap_or(false) { tr!(language, "config.on") } else { tr!(language, "config.off") }
+}
+
+fn describe_config(guild_config: &GuildConfig, language: Language) -> String {
+    let mut content = MessageBuilder::new();
+    content
+        .push_bold(tr!(language, "config.prefix"))
+        .push_line(format!("`{}`", guild_config.prefix.as_deref().unwrap_or(DEFAULT_PREFIX)))
+        .push_bold(tr!(language, "config.language"))
+        .push_line(guild_config.language.as_deref().and_then(|language| language.parse::<Language>().ok()).unwrap_or_default())
+        .push_bold(tr!(language, "config.reminder_channel"));
+
+    match guild_config.reminder_channel {
+        Some(channel_id) => content.channel(ChannelId(channel_id)),
+        None => content.push(tr!(language, "language.not_set")),
+    };
+
+    content.push_line("").push_bold(tr!(language, "config.admin_role"));
+
+    match guild_config.admin_role {
+        Some(role_id) => content.role(RoleId(role_id)),
+        None => content.push(tr!(language, "language.not_set")),
+    };
+
+    content
+        .push_line("")
+        .push_bold(tr!(language, "config.class_roles"))
+        .push_line(on_off(guild_config.class_roles, language))
+        .push_bold(tr!(language, "config.course_channels"))
+        .push_line(on_off(guild_config.course_channels, language))
+        .push_bold(tr!(language, "config.interaction_timeout"))
+        .push_line(tr!(language, "config.minutes", guild_config.interaction_timeout.unwrap_or(DEFAULT_INTERACTION_TIMEOUT)))
+        .push_bold_line(tr!(language, "config.command_groups"));
+    for group in COMMAND_GROUPS.iter() {
+        let status = if guild_config.is_group_enabled(group.name) { tr!(language, "config.enabled") } else { tr!(language, "config.disabled") };
+        content.push_line(format!("- {}: {}", group.name, status));
+    }
+
+    content.build()
+}
+
+#[command]
+#[only_in("guild")]
+#[required_permissions("MANAGE_GUILD")]
+#[description("Show or change the bot settings of this server")]
+#[usage("[prefix | language | reminder_channel | admin_role | class_roles | course_channels | interaction_timeout | enable | disable] [value]")]
+#[example("prefix !")]
+#[example("reminder_channel #reminders")]
+#[example("disable OldBinusmaya")]
+#[example("class_roles on")]
+#[example("interaction_timeout 10")]
+async fn config(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+    let mut guild_config = GUILD_CONFIG
+        .lock()
+        .await
+        .get(guild_id.as_u64())
+        .cloned()
+        .unwrap_or_else(|| GuildConfig::new(*guild_id.as_u64()));
+    let prefix = guild_config.prefix.clone().unwrap_or_else(|| DEFAULT_PREFIX.to_string());
+    let language = msg_language(msg).await;
+
+    let mut args = Args::new(args.message(), &[Delimiter::Single(' '), Delimiter::Single(';')]);
+    let setting = match args.single::<String>() {
+        Ok(setting) => setting.to_ascii_lowercase(),
+        Err(_) => {
+            msg.channel_id.send_message(&ctx.http, |m| {
+                m.embed(|e| e
+                    .title(tr!(language, "config.title"))
+                    .colour(PRIMARY_COLOR)
+                    .description(describe_config(&guild_config, language))
+                    .footer(|f| f.text(tr!(language, "config.footer", prefix)))
+                )
+            }).await?;
+
+            return Ok(());
+        }
+    };
+    let value = args.single::<String>().ok();
+
+    match (setting.as_str(), value) {
+        ("prefix", Some(prefix)) => {
+            guild_config.prefix = if prefix.eq("reset") { None } else { Some(prefix) };
+        }
+        ("language", Some(value)) if value.eq("reset") => guild_config.language = None,
+        ("language", Some(value)) => {
+            match value.parse::<Language>() {
+                Ok(value) => guild_config.language = Some(value.code().to_string()),
+                Err(_) => {
+                    let codes: Vec<&str> = Language::ALL.iter().map(|language| language.code()).collect();
+                    return reply(ctx, msg, &tr!(language, "language.unsupported.title"), tr!(language, "language.unsupported.body", format!("`{}`", codes.join("`, `")))).await;
+                }
+            }
+        }
+        ("reminder_channel", Some(value)) if value.eq("none") => guild_config.reminder_channel = None,
+        ("reminder_channel", Some(value)) => {
+            let channel = match value.parse::<ChannelId>() {
+                Ok(channel_id) => channel_id.to_channel(&ctx).await.ok(),
+                Err(_) => None,
+            };
+
+            match channel {
+                Some(Channel::Guild(channel)) if channel.guild_id == guild_id => guild_config.reminder_channel = Some(*channel.id.as_u64()),
+                _ => return reply(ctx, msg, &tr!(language, "config.channel_not_found.title"), tr!(language, "config.channel_not_found.body", prefix)).await,
+            }
+        }
+        ("admin_role", Some(value)) if value.eq("none") => guild_config.admin_role = None,
+        ("admin_role", Some(value)) => {
+            let roles = guild_id.roles(&ctx.http).await?;
+
+            match value.parse::<RoleId>() {
+                Ok(role_id) if roles.contains_key(&role_id) => guild_config.admin_role = Some(*role_id.as_u64()),
+                _ => return reply(ctx, msg, &tr!(language, "config.role_not_found.title"), tr!(language, "config.role_not_found.body", prefix)).await,
+            }
+        }
+        (setting @ ("class_roles" | "course_channels"), Some(value)) if value.eq("on") || value.eq("off") => {
+            let value = Some(value.eq("on"));
+            if setting.eq("class_roles") {
+                guild_config.class_roles = value;
+            } else {
+                guild_config.course_channels = value;
+            }
+        }
+        ("interaction_timeout", Some(value)) if value.eq("reset") => guild_config.interaction_timeout = None,
+        ("interaction_timeout", Some(value)) => {
+            match value.parse::<u64>() {
+                Ok(minutes) if (1..=MAX_INTERACTION_TIMEOUT).contains(&minutes) => guild_config.interaction_timeout = Some(minutes),
+                _ => return reply(ctx, msg, &tr!(language, "config.invalid_timeout.title"), tr!(language, "config.invalid_timeout.body", MAX_INTERACTION_TIMEOUT)).await,
+            }
+        }
+        (action @ ("enable" | "disable"), Some(group_name)) => {
+            match COMMAND_GROUPS.iter().find(|group| group.name.eq_ignore_ascii_case(&group_name)) {
+                Some(group) => guild_config.set_group_enabled(group.name, action.eq("enable")),
+                None => {
+                    let group_names: Vec<&str> = COMMAND_GROUPS.iter().map(|group| group.name).collect();
+                    return reply(ctx, msg, &tr!(language, "config.group_not_found.title"), tr!(language, "config.group_not_found.body", group_names.join("`, `"))).await;
+                }
+            }
+        }
+        _ => return reply(ctx, msg, &tr!(language, "config.invalid_setting.title"), tr!(language, "config.invalid_setting.body", prefix)).await,
+    }
+
+    if let Err(e) = save_guild_config(guild_config.clone()).await {
+        eprintln!("Failed to store the config of guild {}: {:?}", guild_id, e);
+    }
+
+    if guild_config.class_roles.unwrap_or(false) && (setting.eq("class_roles") || setting.eq("course_channels")) {
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = sync_guild_classes(&ctx, guild_id).await {
+                eprintln!("Failed to sync the class roles of {}: {:?}", guild_id, e);
+            }
+        });
+    }
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title(tr!(language, "config.updated"))
+            .colour(PRIMARY_COLOR)
+            .description(describe_config(&guild_config, language))
+        )
+    }).await?;
+
+    Ok(())
+}
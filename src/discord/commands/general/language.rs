@@ -0,0 +1,56 @@
+use serenity::{
+    framework::standard::{macros::command, Args, CommandResult},
+    model::prelude::*,
+    prelude::*,
+};
+
+use crate::{
+    consts::USER_LANGUAGE,
+    discord::helper::{guild_language, msg_language, reply, save_user_language},
+    locale::Language,
+};
+
+#[command]
+#[description("Show or change the language the bot replies to you in, `reset` follows the language of the server")]
+#[usage("[en | id | reset]")]
+#[example("id")]
+#[example("reset")]
+async fn language(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let user_id = *msg.author.id.as_u64();
+
+    let (language, content) = match args.single::<String>().ok().as_deref() {
+        None => {
+            let language = msg_language(msg).await;
+            let not_set = tr!(language, "language.not_set");
+            let user_language = USER_LANGUAGE.lock().await.get(&user_id).map_or(not_set.clone(), |language| language.to_string());
+            let guild_language = guild_language(msg.guild_id).await.map_or(not_set, |language| language.to_string());
+
+            (language, tr!(language, "language.current", user_language, guild_language))
+        }
+        Some("reset") => {
+            if let Err(e) = save_user_language(user_id, None).await {
+                eprintln!("Failed to store the language of {}: {:?}", user_id, e);
+            }
+            let language = msg_language(msg).await;
+
+            (language, tr!(language, "language.reset"))
+        }
+        Some(value) => match value.parse::<Language>() {
+            Ok(language) => {
+                if let Err(e) = save_user_language(user_id, Some(language)).await {
+                    eprintln!("Failed to store the language of {}: {:?}", user_id, e);
+                }
+
+                (language, tr!(language, "language.updated", language))
+            }
+            Err(_) => {
+                let language = msg_language(msg).await;
+                let codes: Vec<&str> = Language::ALL.iter().map(|language| language.code()).collect();
+
+                return reply(ctx, msg, &tr!(language, "language.unsupported.title"), tr!(language, "language.unsupported.body", format!("`{}`", codes.join("`, `")))).await;
+            }
+        },
+    };
+
+    reply(ctx, msg, &tr!(language, "language.title"), content).await
+}
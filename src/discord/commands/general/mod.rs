@@ -1,4 +1,8 @@
 pub mod about;
+pub mod account;
 pub mod add;
+pub mod config;
+pub mod language;
 pub mod ping;
+pub mod profile;
 pub mod register;
\ No newline at end of file
@@ -0,0 +1,113 @@
+use chrono::{Duration, Local};
+use serenity::{
+    framework::standard::{macros::command, Args, CommandResult},
+    model::prelude::*,
+    prelude::*,
+    utils::MessageBuilder,
+};
+use std::ops::Add;
+
+use crate::{
+    consts::{NEWBINUSMAYA_USER_DATA, OLDBINUSMAYA_USER_DATA, PRIMARY_COLOR},
+    discord::helper::{get_old_binusmaya_api, guild_prefix, msg_language, new_binusmaya_api, parse_account_arg},
+    locale::Language,
+};
+
+async fn new_binusmaya_status(account_key: &(u64, String), language: Language) -> String {
+    match NEWBINUSMAYA_USER_DATA.lock().await.get(account_key) {
+        Some(user_auth_info) => {
+            let jwt_exp = user_auth_info.last_registered.add(Duration::weeks(52));
+
+            if jwt_exp > Local::now() {
+                tr!(language, "profile.token_valid", jwt_exp.format("%F"))
+            } else {
+                tr!(language, "profile.token_expired", jwt_exp.format("%F"))
+            }
+        }
+        None => tr!(language, "profile.not_registered"),
+    }
+}
+
+async fn old_binusmaya_status(account_key: &(u64, String), language: Language) -> String {
+    match OLDBINUSMAYA_USER_DATA.lock().await.get(account_key) {
+        Some(user_auth_info) if user_auth_info.login_failed => tr!(language, "profile.login_failed"),
+        Some(_) => tr!(language, "profile.registered"),
+        None => tr!(language, "profile.not_registered"),
+    }
+}
+
+#[command]
+#[aliases("me")]
+#[usage("[--account [account name]]")]
+#[description("Show your BINUSMAYA profile, your NIM and email are only shown in DM")]
+pub async fn profile(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, _) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let account_key = (*msg.author.id.as_u64(), account.clone());
+    let language = msg_language(msg).await;
+    let show_sensitive = msg.guild_id.is_none();
+    let sensitive = |value: &str| if show_sensitive { value.to_string() } else { tr!(language, "profile.hidden") };
+
+    let user_profile = match new_binusmaya_api(&account_key).await {
+        Some(binusmaya_api) => binusmaya_api.get_user_profile().await.ok(),
+        None => None,
+    };
+    let binusian_data = match get_old_binusmaya_api(&ctx.http, &account_key).await {
+        Ok(old_binusmaya_api) => old_binusmaya_api.get_binusian_data().await.ok(),
+        Err(_) => None,
+    };
+
+    let mut info = MessageBuilder::new();
+    if let Some(user_profile) = &user_profile {
+        info.push_bold(tr!(language, "profile.person_code")).push_line(sensitive(&user_profile.person_code));
+        info.push_bold(tr!(language, "profile.email")).push_line(sensitive(&user_profile.email));
+
+        if let Some(role) = user_profile.active_role() {
+            info.push_bold(tr!(language, "profile.active_role")).push_line(&role.name);
+            info.push_bold(tr!(language, "profile.programme")).push_line(&role.academic_program_desc);
+            info.push_bold(tr!(language, "profile.career")).push_line(&role.academic_career_desc);
+            info.push_bold(tr!(language, "profile.institution")).push_line(&role.institution_desc);
+        }
+    }
+
+    if let Some(binusian_data) = &binusian_data {
+        info.push_bold(tr!(language, "profile.nim")).push_line(sensitive(&binusian_data.nim));
+
+        if user_profile.is_none() {
+            info.push_bold(tr!(language, "profile.email")).push_line(sensitive(&binusian_data.email));
+            info.push_bold(tr!(language, "profile.career")).push_line(&binusian_data.acad_career);
+            info.push_bold(tr!(language, "profile.institution")).push_line(&binusian_data.institution);
+        }
+    }
+
+    if user_profile.is_none() && binusian_data.is_none() {
+        info.push(tr!(language, "profile.unavailable", guild_prefix(msg.guild_id).await));
+    }
+
+    let full_name = match (&user_profile, &binusian_data) {
+        (Some(user_profile), _) => user_profile.full_name.clone(),
+        (None, Some(binusian_data)) => format!("{} {}", binusian_data.first_name, binusian_data.last_name),
+        (None, None) => msg.author.name.clone(),
+    };
+    let picture_url = user_profile.as_ref().and_then(|user_profile| user_profile.user_picture_url.clone());
+    let new_binusmaya_status = new_binusmaya_status(&account_key, language).await;
+    let old_binusmaya_status = old_binusmaya_status(&account_key, language).await;
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| {
+            e.title(full_name)
+                .colour(PRIMARY_COLOR)
+                .description(info.build())
+                .field(tr!(language, "common.new_binusmaya"), new_binusmaya_status, true)
+                .field(tr!(language, "common.old_binusmaya"), old_binusmaya_status, true)
+                .footer(|f| f.text(tr!(language, "profile.footer", account)));
+
+            if let Some(picture_url) = picture_url {
+                e.thumbnail(picture_url);
+            }
+
+            e
+        })
+    }).await?;
+
+    Ok(())
+}
@@ -3,7 +3,10 @@ use serenity::framework::standard::CommandResult;
 use serenity::model::prelude::*;
 use serenity::prelude::*;
 
-use crate::consts::PRIMARY_COLOR;
+use crate::{
+    consts::{DEFAULT_PREFIX, PRIMARY_COLOR},
+    discord::helper::msg_language,
+};
 
 #[command]
 #[description("Receive a DM to register your binus account for additional features")]
@@ -11,12 +14,15 @@ use crate::consts::PRIMARY_COLOR;
 pub async fn register(ctx: &Context, msg: &Message) -> CommandResult {
     msg.react(&ctx, '👍').await?;
 
+    let language = msg_language(msg).await;
+
     msg.author
         .dm(&ctx, |m| {
             m.embed(|e| {
                 e.colour(PRIMARY_COLOR).field(
-                    "Register",
-                    "Please enter your BINUS email and password, e.g. `=add [email];[password]`",
+                    tr!(language, "register.title"),
+                    // the commands are used in the DM, where the default prefix applies
+                    tr!(language, "register.body", DEFAULT_PREFIX, DEFAULT_PREFIX),
                     false,
                 )
             })
@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod general;
 pub mod old_binusmaya;
 pub mod new_binusmaya;
\ No newline at end of file
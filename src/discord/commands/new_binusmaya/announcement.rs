@@ -1,180 +1,485 @@
-use chrono::Duration;
-use pcre2::bytes::RegexBuilder;
+use chrono::NaiveDate;
+use futures::StreamExt;
 use serenity::{
-    framework::standard::{macros::command, CommandResult},
+    framework::standard::{macros::command, Args, CommandResult, Delimiter},
+    http::AttachmentType,
     model::{prelude::*, interactions::message_component::ButtonStyle},
-    prelude::*, builder::CreateButton,
+    prelude::*, builder::{CreateActionRow, CreateButton, CreateSelectMenu, CreateSelectMenuOption},
 };
-use std::ops::Add;
-
+use std::{borrow::Cow, str::FromStr};
 use crate::{
-    api::new_binusmaya_api::{AnnouncementDetails, AnnouncementResponse, NewBinusmayaAPI},
-    consts::{PRIMARY_COLOR, NEWBINUSMAYA_USER_DATA},
+    api::new_binusmaya_api::{Announcement, NewBinusmayaAPI},
+    consts::PRIMARY_COLOR,
+    discord::{
+        archive::{bundle_files, url_file_name, ArchiveFile, MAX_UPLOAD_SIZE},
+        helper::{button_label, disable_components, interaction_timeout, is_invoker, msg_language, new_binusmaya_api_or_reply, parse_account_arg, reply, Nav},
+        markdown::{html_to_markdown, split_text, EMBED_DESCRIPTION_LIMIT},
+        render,
+    },
+    locale::Language,
 };
 
-fn parse_html(mut content: String) -> String {
-    let mut parsed_content = String::new();
-    let content_clone = content.clone();
-
-    let reg = RegexBuilder::new().build(r"(?P<open_tag><.*?>)").unwrap();
-    
-    for tags in reg.captures_iter(&content_clone.as_bytes()) {
-        let caps = tags.unwrap();
-        let open_tag = std::str::from_utf8(&caps["open_tag"]).unwrap();
-
-        if open_tag.contains("<li") {
-           content = content.replace(open_tag,"- ");
-        } else if open_tag.contains("<strong") || open_tag.eq("</strong>"){
-           content = content.replace(open_tag, "**");
-        } else if open_tag.contains("<em") || open_tag.eq("</em>") {
-           content = content.replace(open_tag, "*");
-        } else if open_tag.contains("<span") || open_tag.eq("</span>") {
-           content = content.replace(open_tag, "__");
-        } else if open_tag.eq("</p>") || open_tag.eq("<br />"){
-           content = content.replace(open_tag, "\n");
-        } else {
-           content = content.replace(open_tag, "");
+const ANNOUNCEMENTS_PER_PAGE: usize = 10;
+const ANNOUNCEMENT_SELECT: &str = "announcement_select";
+/// 4 rows of link buttons, the last row is left for the navigation buttons
+const LINKS_PER_PAGE: usize = 20;
+/// Discord accepts up to 10 files in a message
+const MAX_FILES_PER_MESSAGE: usize = 10;
+
+/// The search and filters given to the command, e.g. `exam --career undergraduate --from 2022-03-01`
+#[derive(Default, Debug, PartialEq)]
+struct AnnouncementFilter {
+    search: Option<String>,
+    career: Option<String>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+}
+
+/// Why the filters given to the command can't be used, translated when it's replied
+#[derive(Debug, PartialEq)]
+enum FilterError {
+    MissingValue(String),
+    InvalidDate(String),
+}
+
+impl FilterError {
+    fn message(&self, language: Language) -> String {
+        match self {
+            Self::MissingValue(arg) => tr!(language, "announcement.missing_value", arg),
+            Self::InvalidDate(value) => tr!(language, "announcement.invalid_date", value),
+        }
+    }
+}
+
+impl AnnouncementFilter {
+    fn parse<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Self, FilterError> {
+        let mut filter = AnnouncementFilter::default();
+        let mut search: Vec<&str> = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg {
+                "--career" | "--from" | "--to" => {
+                    let value = args.next().ok_or_else(|| FilterError::MissingValue(arg.to_string()))?;
+                    match arg {
+                        "--career" => filter.career = Some(value.to_lowercase()),
+                        _ => {
+                            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                                .map_err(|_| FilterError::InvalidDate(value.to_string()))?;
+                            if arg == "--from" { filter.from = Some(date) } else { filter.to = Some(date) }
+                        }
+                    }
+                }
+                _ => search.push(arg),
+            }
+        }
+
+        if !search.is_empty() {
+            filter.search = Some(search.join(" ").to_lowercase());
+        }
+
+        Ok(filter)
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == AnnouncementFilter::default()
+    }
+
+    /// An announcement matches the date range if it's shown at any time between `from` and `to`
+    fn matches(&self, announcement: &Announcement) -> bool {
+        let start_date = NaiveDate::parse_from_str(&announcement.start_date, "%FT%X").ok();
+        let end_date = NaiveDate::parse_from_str(&announcement.end_date, "%FT%X").ok();
+
+        self.search.as_ref().is_none_or(|search| announcement.title.to_lowercase().contains(search))
+            && self.career.as_ref().is_none_or(|career| announcement.academic_career_desc.to_lowercase().contains(career))
+            && self.from.is_none_or(|from| end_date.is_none_or(|end_date| end_date >= from))
+            && self.to.is_none_or(|to| start_date.is_none_or(|start_date| start_date <= to))
+    }
+}
+
+fn describe_page(announcements: &[Announcement], page: usize, language: Language) -> String {
+    let start = page * ANNOUNCEMENTS_PER_PAGE;
+    let end = (start + ANNOUNCEMENTS_PER_PAGE).min(announcements.len());
+
+    render::announcements(announcements.get(start..end).unwrap_or_default(), start + 1, language)
+}
+
+fn page_count(announcements: &[Announcement]) -> usize {
+    announcements.len().div_ceil(ANNOUNCEMENTS_PER_PAGE).max(1)
+}
+
+fn announcement_select_menu(announcements: &[Announcement], page: usize, language: Language) -> CreateSelectMenu {
+    let mut menu = CreateSelectMenu::default();
+    menu.custom_id(ANNOUNCEMENT_SELECT);
+    menu.placeholder(tr!(language, "announcement.select"));
+    menu.options(|f| {
+        for (i, announcement) in announcements.iter().enumerate().skip(page * ANNOUNCEMENTS_PER_PAGE).take(ANNOUNCEMENTS_PER_PAGE) {
+            let mut opt = CreateSelectMenuOption::default();
+            opt.label(button_label(&format!("{}. {}", i + 1, announcement.title)));
+            opt.value(&announcement.id);
+            f.add_option(opt);
+        }
+
+        f
+    });
+
+    menu
+}
+
+fn is_paginated(announcements: &[Announcement]) -> bool {
+    page_count(announcements) > 1
+}
+
+fn page_components(announcements: &[Announcement], page: usize, language: Language) -> Vec<CreateActionRow> {
+    let mut rows = Vec::new();
+
+    if is_paginated(announcements) {
+        rows.push(Nav::action_row());
+    }
+
+    if !announcements.is_empty() {
+        let mut ar = CreateActionRow::default();
+        ar.add_select_menu(announcement_select_menu(announcements, page, language));
+        rows.push(ar);
+    }
+
+    rows
+}
+
+fn page_footer(announcements: &[Announcement], page: usize, language: Language) -> String {
+    tr!(language, "announcement.footer", page + 1, page_count(announcements), announcements.len())
+}
+
+/// Downloads the attachments that fit in an upload, the others are returned as links
+async fn download_attachments(binusmaya_api: &NewBinusmayaAPI, attachment_links: &[Option<String>]) -> (Vec<ArchiveFile>, Vec<String>) {
+    let mut files: Vec<ArchiveFile> = Vec::new();
+    let mut links: Vec<String> = Vec::new();
+
+    for link in attachment_links.iter().flatten().filter(|link| !link.trim().is_empty()) {
+        match binusmaya_api.download_resource(link, MAX_UPLOAD_SIZE).await {
+            Ok(Some((content_type, content))) if !content_type.as_deref().is_some_and(|content_type| content_type.starts_with("text/html")) => {
+                files.push((attachment_name(link), content));
+            }
+            Ok(_) => links.push(link.clone()),
+            Err(e) => {
+                eprintln!("Failed to download attachment {}: {:?}", link, e);
+                links.push(link.clone());
+            }
         }
+    }
+
+    (files, links)
+}
 
-       content = content.replace("&nbsp;", " ").replace("&ndash;", "-").replace("&amp;", "&").replace("&ge;", "≥").replace("&zwj;", "").replace("&bull;", "- ");     
+fn attachment_name(link: &str) -> String {
+    match url_file_name(link) {
+        "" => "attachment".to_string(),
+        file_name => file_name.replace("%20", " "),
     }
+}
 
-    parsed_content.push_str(&content); 
-    
-    parsed_content
+fn link_page_count(links: &[String]) -> usize {
+    links.len().div_ceil(LINKS_PER_PAGE).max(1)
+}
+
+fn attachment_link_rows(links: &[String], page: usize) -> Vec<CreateActionRow> {
+    let mut rows: Vec<CreateActionRow> = links
+        .iter()
+        .skip(page * LINKS_PER_PAGE)
+        .take(LINKS_PER_PAGE)
+        .collect::<Vec<_>>()
+        .chunks(5)
+        .map(|row| {
+            let mut ar = CreateActionRow::default();
+            for link in row {
+                let mut btn = CreateButton::default();
+                btn.style(ButtonStyle::Link);
+                btn.url(link);
+                btn.label(button_label(&attachment_name(link)));
+
+                ar.add_button(btn);
+            }
+
+            ar
+        })
+        .collect();
+
+    if link_page_count(links) > 1 {
+        rows.push(Nav::action_row());
+    }
+
+    rows
+}
+
+/// Pages through the attachment link buttons in the background so the announcement browser stays responsive
+fn paginate_attachment_links(ctx: &Context, msg: &Message, mut message: Message, links: Vec<String>) {
+    let ctx = ctx.clone();
+    let msg = msg.clone();
+
+    tokio::spawn(async move {
+        let mut page: usize = 0;
+        let mut cib = message.await_component_interactions(&ctx).timeout(interaction_timeout(msg.guild_id).await).await;
+
+        while let Some(mci) = cib.next().await {
+            match is_invoker(&ctx, &msg, &mci).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    eprintln!("Failed to reply to the attachment links: {:?}", e);
+                    continue;
+                }
+            }
+
+            page = match Nav::from_str(&mci.data.custom_id) {
+                Ok(Nav::Previous) => page.saturating_sub(1),
+                Ok(Nav::Next) => (page + 1).min(link_page_count(&links) - 1),
+                Err(_) => continue,
+            };
+
+            let result = mci.create_interaction_response(&ctx, |r| {
+                r.kind(InteractionResponseType::UpdateMessage);
+                r.interaction_response_data(|d| d.components(|c| c.set_action_rows(attachment_link_rows(&links, page))))
+            }).await;
+
+            if let Err(e) = result {
+                eprintln!("Failed to update the attachment links: {:?}", e);
+            }
+        }
+
+        if let Err(e) = disable_components(&ctx, &mut message).await {
+            eprintln!("Failed to disable the attachment links: {:?}", e);
+        }
+    });
 }
 
 async fn send_announcement_details(
     ctx: &Context,
     msg: &Message,
     binusmaya_api: &NewBinusmayaAPI,
-    announcement_list: &AnnouncementResponse,
-) {
-    let announcement_details: Option<AnnouncementDetails>;
-
-    if let Some(answer) = &msg
-        .author
-        .await_reply(&ctx)
-        .timeout(Duration::seconds(30).to_std().unwrap())
-        .await
-    {
-        let reply: usize = answer.content.parse().unwrap_or(0);
-        if reply == 0 {
-            return;
+    announcement_id: &str,
+) -> CommandResult {
+    let channel_id = msg.channel_id;
+    let language = msg_language(msg).await;
+    let details = match binusmaya_api.get_announcement_details(announcement_id).await.unwrap_or(None) {
+        Some(details) => details,
+        None => {
+            channel_id.send_message(&ctx, |m| {
+                m.embed(|e| e.title(tr!(language, "announcement.not_found.title")).description(tr!(language, "announcement.not_found.body")).colour(PRIMARY_COLOR))
+            }).await?;
+            return Ok(());
         }
-        announcement_details = binusmaya_api
-            .get_announcement_details(&announcement_list.announcements[reply - 1].id)
-            .await
-            .unwrap_or(None);
-
-        if let Some(details) = announcement_details {
-            msg.channel_id
-                .send_message(&ctx, |m| {
-                    m.embed(|e| e.title(details.title).description(parse_html(details.content)).colour(PRIMARY_COLOR));
-                    m.components(|f| {
-                        f.create_action_row(|ar| {
-                            if details.attachment_links.is_empty() {
-                                let mut btn = CreateButton::default();
-                                btn.style(ButtonStyle::Link);
-                                btn.url("https://www.google.com");
-                                btn.label("Attachment Link");
-                                btn.disabled(true);
-
-                                ar.add_button(btn);
-                            } else {
-                                details.attachment_links.iter().for_each(|link| {
-                                    let mut btn = CreateButton::default();
-                                    btn.style(ButtonStyle::Link);
-                                    btn.url(link.clone().unwrap());
-                                    btn.label("Attachment Link");
-                                    btn.disabled(false);
-    
-                                    ar.add_button(btn);
-                                });
-                            }
-    
-    
-                            ar
-                        })
-                    })
+    };
+
+    channel_id.broadcast_typing(&ctx.http).await?;
+
+    let (files, links) = download_attachments(binusmaya_api, &details.attachment_links).await;
+    let mut pages = split_text(&html_to_markdown(&details.content), EMBED_DESCRIPTION_LIMIT);
+    let last_page = pages.pop().unwrap_or_default();
+
+    // the title is only shown on the first embed and the details of the announcement on the last one
+    for (i, page) in pages.iter().enumerate() {
+        channel_id
+            .send_message(&ctx, |m| {
+                m.embed(|e| {
+                    if i == 0 {
+                        e.title(&details.title);
+                    }
+                    e.description(page).colour(PRIMARY_COLOR)
+                })
+            })
+            .await?;
+    }
+
+    let message = channel_id
+        .send_message(&ctx, |m| {
+            m.embed(|e| {
+                if pages.is_empty() {
+                    e.title(&details.title);
+                }
+                e.description(last_page)
+                    .colour(PRIMARY_COLOR)
+                    .field(tr!(language, "announcement.mandatory"), tr!(language, if details.is_mandatory { "common.yes" } else { "common.no" }), true);
+
+                if let Some(link_url) = details.link_url.as_ref().filter(|link_url| !link_url.trim().is_empty()) {
+                    e.field(tr!(language, "announcement.link"), link_url, true);
+                }
+
+                e
+            });
+
+            if !links.is_empty() {
+                m.components(|c| c.set_action_rows(attachment_link_rows(&links, 0)));
+            }
+
+            m
+        })
+        .await?;
+
+    for bundle in bundle_files(files) {
+        for chunk in bundle.chunks(MAX_FILES_PER_MESSAGE) {
+            let attachments: Vec<AttachmentType> = chunk
+                .iter()
+                .map(|(name, content)| AttachmentType::Bytes {
+                    data: Cow::from(content.as_slice()),
+                    filename: name.clone(),
                 })
-                .await
-                .unwrap();
-        } else {
-            msg.channel_id.send_message(&ctx, |m| {
-                m.embed(|e| e.title("Error").description("Object reference not set to an instance of an object").colour(PRIMARY_COLOR))
-            }).await.unwrap();
+                .collect();
+
+            channel_id.send_files(&ctx.http, attachments, |m| m).await?;
         }
+    }
 
-    } else {
-        return;
+    if link_page_count(&links) > 1 {
+        paginate_attachment_links(ctx, msg, message, links);
     }
+
+    Ok(())
+}
+
+/// Sends the details in the background, downloading the attachments would otherwise stall the other clicks
+fn spawn_announcement_details(ctx: &Context, msg: &Message, binusmaya_api: &NewBinusmayaAPI, announcement_id: &str) {
+    let ctx = ctx.clone();
+    let msg = msg.clone();
+    let binusmaya_api = binusmaya_api.clone();
+    let announcement_id = announcement_id.to_string();
+
+    tokio::spawn(async move {
+        if let Err(e) = send_announcement_details(&ctx, &msg, &binusmaya_api, &announcement_id).await {
+            eprintln!("Failed to send the announcement details: {:?}", e);
+        }
+    });
 }
 
 #[command]
-#[description("Get the announcements in new binusmaya")]
-async fn announcement(ctx: &Context, msg: &Message) -> CommandResult {
-    let user_data = NEWBINUSMAYA_USER_DATA.clone();
-
-    if user_data.lock().await.contains_key(msg.author.id.as_u64()) {
-        let jwt_exp = user_data
-            .lock()
-            .await
-            .get(msg.author.id.as_u64())
-            .unwrap()
-            .last_registered
-            .add(Duration::weeks(52));
-        let now = chrono::offset::Local::now();
-        if jwt_exp > now {
-            let binusmaya_api = NewBinusmayaAPI {
-                token: user_data
-                    .lock()
-                    .await
-                    .get(msg.author.id.as_u64())
-                    .unwrap()
-                    .auth
-                    .clone(),
-            };
-            // let mut page = 1;
-            let announcement_list = binusmaya_api.get_announcement(1).await?;
-
-            msg.channel_id
-                .send_message(&ctx.http, |m| {
-                    m.embed(|e| {
-                        e.title("Type the number to see the content")
-                            .description(&announcement_list)
-                            .colour(PRIMARY_COLOR)
-                            .footer(|f| f.text("Timeout in 30 seconds"))
-                    })
-                })
-                .await?;
-
-            send_announcement_details(ctx, msg, &binusmaya_api, &announcement_list).await;
-        } else {
-            msg.channel_id
-                .send_message(&ctx.http, |m| {
-                    m.embed(|e| {
-                        e.colour(PRIMARY_COLOR).field(
-                            "Your bearer token has expired",
-                            "please re-register using `=add` command",
-                            false,
-                        )
-                    })
-                })
-                .await?;
+#[description("Browse the announcements in new binusmaya, search by title and filter by academic career or date")]
+#[usage("[title] [--career [academic career]] [--from YYYY-MM-DD] [--to YYYY-MM-DD] [--account [account name]]")]
+#[example("exam --career undergraduate --from 2022-03-01")]
+async fn announcement(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, args) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let language = msg_language(msg).await;
+    // the title and filters are separated by spaces
+    let args = Args::new(args.message(), &[Delimiter::Single(' ')]);
+    let filter = match AnnouncementFilter::parse(args.raw_quoted()) {
+        Ok(filter) => filter,
+        Err(e) => return reply(ctx, msg, &tr!(language, "announcement.invalid_filter"), e.message(language)).await,
+    };
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+
+    msg.channel_id.broadcast_typing(&ctx.http).await?;
+
+    let announcements: Vec<Announcement> = binusmaya_api.get_all_announcements()
+        .await?
+        .into_iter()
+        .filter(|announcement| filter.matches(announcement))
+        .collect();
+    let title = tr!(language, if filter.is_empty() { "announcement.title" } else { "announcement.search_title" });
+    let mut page: usize = 0;
+
+    let mut m = msg.channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(&title)
+                    .description(describe_page(&announcements, page, language))
+                    .colour(PRIMARY_COLOR)
+                    .footer(|f| f.text(page_footer(&announcements, page, language)))
+            });
+            m.components(|c| c.set_action_rows(page_components(&announcements, page, language)))
+        })
+        .await?;
+
+    let mut cib = m.await_component_interactions(&ctx).timeout(interaction_timeout(msg.guild_id).await).await;
+    while let Some(mci) = cib.next().await {
+        if !is_invoker(ctx, msg, &mci).await? {
+            continue;
         }
-    } else {
-        msg.channel_id
-            .send_message(&ctx.http, |m| {
-                m.embed(|e| {
-                    e.colour(PRIMARY_COLOR).field(
-                        "You're not registered",
-                        "please register first using `=register` command",
-                        false,
-                    )
-                })
+
+        if mci.data.custom_id == ANNOUNCEMENT_SELECT {
+            mci.create_interaction_response(&ctx, |r| r.kind(InteractionResponseType::DeferredUpdateMessage)).await?;
+
+            if let Some(announcement_id) = mci.data.values.first() {
+                spawn_announcement_details(ctx, msg, &binusmaya_api, announcement_id);
+            }
+            continue;
+        }
+
+        page = match Nav::from_str(&mci.data.custom_id) {
+            Ok(Nav::Previous) => page.saturating_sub(1),
+            Ok(Nav::Next) => (page + 1).min(page_count(&announcements) - 1),
+            Err(_) => continue,
+        };
+
+        mci.create_interaction_response(&ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage);
+            r.interaction_response_data(|d| {
+                d.create_embed(|e| {
+                    e.title(&title)
+                        .description(describe_page(&announcements, page, language))
+                        .colour(PRIMARY_COLOR)
+                        .footer(|f| f.text(page_footer(&announcements, page, language)))
+                });
+                d.components(|c| c.set_action_rows(page_components(&announcements, page, language)))
             })
-            .await?;
+        }).await?;
     }
+
+    disable_components(ctx, &mut m).await?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn announcement(title: &str, career: &str, start_date: &str, end_date: &str) -> Announcement {
+        serde_json::from_value(serde_json::json!({
+            "academicCareerDesc": career,
+            "announcementMasterId": "1",
+            "id": "1",
+            "title": title,
+            "startDate": start_date,
+            "endDate": end_date,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_filter_test() {
+        let filter = AnnouncementFilter::parse(vec!["Final", "Exam", "--career", "Undergraduate", "--from", "2022-03-01"].into_iter()).unwrap();
+
+        assert_eq!(filter.search, Some("final exam".to_string()));
+        assert_eq!(filter.career, Some("undergraduate".to_string()));
+        assert_eq!(filter.from, NaiveDate::from_ymd_opt(2022, 3, 1));
+        assert_eq!(filter.to, None);
+        assert_eq!(AnnouncementFilter::parse(vec!["--to", "tomorrow"].into_iter()), Err(FilterError::InvalidDate("tomorrow".to_string())));
+        assert_eq!(AnnouncementFilter::parse(vec!["--career"].into_iter()), Err(FilterError::MissingValue("--career".to_string())));
+        assert!(AnnouncementFilter::parse(Vec::new().into_iter()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn attachment_links_test() {
+        let links: Vec<String> = (0..21).map(|i| format!("https://example.com/files/file%20{}.pdf", i)).collect();
+
+        assert_eq!(attachment_name(&links[0]), "file 0.pdf");
+        assert_eq!(attachment_name("https://example.com"), "attachment");
+        assert_eq!(link_page_count(&links), 2);
+        assert_eq!(link_page_count(&links[..20]), 1);
+        assert_eq!(attachment_link_rows(&links, 0).len(), 5);
+        assert_eq!(attachment_link_rows(&links, 1).len(), 2);
+    }
+
+    #[test]
+    fn filter_matches_test() {
+        let exam = announcement("Final Exam Schedule", "Undergraduate", "2022-03-01T00:00:00", "2022-03-10T00:00:00");
+        let filter = |args: Vec<&str>| AnnouncementFilter::parse(args.into_iter()).unwrap();
+
+        assert!(filter(vec!["exam"]).matches(&exam));
+        assert!(!filter(vec!["holiday"]).matches(&exam));
+        assert!(!filter(vec!["--career", "master"]).matches(&exam));
+        assert!(filter(vec!["--from", "2022-03-05", "--to", "2022-04-01"]).matches(&exam));
+        assert!(!filter(vec!["--from", "2022-03-11"]).matches(&exam));
+        assert!(!filter(vec!["--to", "2022-02-28"]).matches(&exam));
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    framework::standard::{macros::command, Args, CommandResult},
+    model::prelude::*,
+    prelude::*,
+    utils::MessageBuilder,
+};
+
+use crate::{
+    api::new_binusmaya_api::Class,
+    consts::{DISCOVERY_FILE, PRIMARY_COLOR},
+    discord::helper::{cached_classes, guild_prefix, matches_course, msg_language, overwrite_records, read_records, reply},
+};
+
+/// A member who agreed to be listed to their classmates in the guild,
+/// records of the global opt-ins from before it was per guild are skipped
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiscoveryRecord {
+    pub guild_id: u64,
+    pub member_id: u64,
+}
+
+async fn set_discoverable(ctx: &Context, msg: &Message, guild_id: GuildId, discoverable: bool) -> CommandResult {
+    let guild_id = *guild_id.as_u64();
+    let member_id = *msg.author.id.as_u64();
+    let mut discovery_records = read_records::<DiscoveryRecord>(DISCOVERY_FILE).await;
+    discovery_records.retain(|record| record.guild_id != guild_id || record.member_id != member_id);
+
+    if discoverable {
+        discovery_records.push(DiscoveryRecord { guild_id, member_id });
+    }
+
+    overwrite_records(DISCOVERY_FILE, &discovery_records).await.map_err(|e| e.to_string())?;
+
+    let language = msg_language(msg).await;
+    if discoverable {
+        reply(ctx, msg, &tr!(language, "classmates.discoverable.title"), tr!(language, "classmates.discoverable.body", guild_prefix(msg.guild_id).await)).await
+    } else {
+        reply(ctx, msg, &tr!(language, "classmates.hidden.title"), tr!(language, "classmates.hidden.body")).await
+    }
+}
+
+/// The course name, the members in the same class and the members in another class of the course
+type CourseClassmates = (String, Vec<UserId>, Vec<(UserId, String)>);
+
+#[command]
+#[only_in("guild")]
+#[description("List the members of this server who share a class or course with you. Only members who run `=classmates optin` are listed, and you have to opt in to see them")]
+#[usage("[optin | optout | course code or name]")]
+#[example("COMP6047")]
+#[example("optin")]
+async fn classmates(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+    let course = args.remains().map(|course| course.trim().to_string());
+
+    match course.as_deref() {
+        Some("optin") => return set_discoverable(ctx, msg, guild_id, true).await,
+        Some("optout") => return set_discoverable(ctx, msg, guild_id, false).await,
+        _ => {}
+    }
+
+    let language = msg_language(msg).await;
+    let member_id = *msg.author.id.as_u64();
+    let discoverable_members: Vec<u64> = read_records::<DiscoveryRecord>(DISCOVERY_FILE)
+        .await
+        .into_iter()
+        .filter(|record| record.guild_id == *guild_id.as_u64())
+        .map(|record| record.member_id)
+        .collect();
+
+    if !discoverable_members.contains(&member_id) {
+        return reply(ctx, msg, &tr!(language, "classmates.not_discoverable.title"), tr!(language, "classmates.not_discoverable.body", guild_prefix(msg.guild_id).await)).await;
+    }
+
+    let my_classes: Vec<Class> = match cached_classes(&member_id).await {
+        Some(classes) => classes.into_iter().filter(|class| matches_course(class, &course)).collect(),
+        None => return reply(ctx, msg, &tr!(language, "classmates.no_classes.title"), tr!(language, "classmates.no_classes.body")).await,
+    };
+
+    let mut courses: BTreeMap<String, CourseClassmates> = my_classes
+        .iter()
+        .map(|class| (class.course_code.clone(), (class.course_name.clone(), Vec::new(), Vec::new())))
+        .collect();
+
+    for other_member_id in discoverable_members.into_iter().filter(|id| *id != member_id) {
+        if guild_id.member(ctx, other_member_id).await.is_err() {
+            continue;
+        }
+
+        let other_classes = match cached_classes(&other_member_id).await {
+            Some(classes) => classes,
+            None => continue,
+        };
+
+        for other_class in other_classes {
+            let my_class = my_classes.iter().find(|class| class.course_code.eq(&other_class.course_code));
+
+            if let (Some(my_class), Some((_, same_class, same_course))) = (my_class, courses.get_mut(&other_class.course_code)) {
+                if my_class.class_Code.eq(&other_class.class_Code) {
+                    same_class.push(UserId(other_member_id));
+                } else {
+                    same_course.push((UserId(other_member_id), other_class.class_Code));
+                }
+            }
+        }
+    }
+
+    let mut content = MessageBuilder::new();
+    for (course_code, (course_name, same_class, same_course)) in courses.iter() {
+        content.push_bold_line(format!("{} - {}", course_code, course_name));
+
+        if same_class.is_empty() && same_course.is_empty() {
+            content.push_quote_line(tr!(language, "classmates.none"));
+        }
+
+        if !same_class.is_empty() {
+            let mentions: Vec<String> = same_class.iter().map(|user_id| user_id.mention().to_string()).collect();
+            content.push_quote_line(tr!(language, "classmates.same_class", mentions.join(", ")));
+        }
+
+        if !same_course.is_empty() {
+            let mentions: Vec<String> = same_course
+                .iter()
+                .map(|(user_id, class_code)| format!("{} ({})", user_id.mention(), class_code))
+                .collect();
+            content.push_quote_line(tr!(language, "classmates.same_course", mentions.join(", ")));
+        }
+
+        content.push_line("");
+    }
+
+    if courses.is_empty() {
+        content.push(tr!(language, "classes.no_match"));
+    }
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title(tr!(language, "classmates.title"))
+            .colour(PRIMARY_COLOR)
+            .description(content.build())
+            .footer(|f| f.text(tr!(language, "classmates.footer")))
+        )
+    }).await?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use serenity::{
+    framework::standard::{macros::command, Args, CommandResult},
+    model::prelude::*,
+    prelude::*,
+};
+
+use crate::{
+    consts::{LEADERBOARD_FILE, PRIMARY_COLOR},
+    discord::{
+        helper::{guild_prefix, msg_language, overwrite_records, read_records, reply},
+        xp_leaderboard::{describe_ranking, leaderboard_members, member_xp, rank, LeaderboardRecord},
+    },
+};
+
+async fn set_ranked(ctx: &Context, msg: &Message, ranked: bool) -> CommandResult {
+    let member_id = *msg.author.id.as_u64();
+    let mut leaderboard_records = read_records::<LeaderboardRecord>(LEADERBOARD_FILE).await;
+    leaderboard_records.retain(|record| record.member_id != member_id);
+
+    if ranked {
+        leaderboard_records.push(LeaderboardRecord { member_id });
+    }
+
+    overwrite_records(LEADERBOARD_FILE, &leaderboard_records).await.map_err(|e| e.to_string())?;
+
+    let language = msg_language(msg).await;
+    if ranked {
+        reply(ctx, msg, &tr!(language, "leaderboard.joined.title"), tr!(language, "leaderboard.joined.body", guild_prefix(msg.guild_id).await)).await
+    } else {
+        reply(ctx, msg, &tr!(language, "leaderboard.left.title"), tr!(language, "leaderboard.left.body")).await
+    }
+}
+
+#[command]
+#[only_in("guild")]
+#[aliases("lb")]
+#[description("Rank the members of this server by their new binusmaya XP. Only registered members who run `=leaderboard optin` are ranked")]
+#[usage("[optin | optout]")]
+#[example("optin")]
+async fn leaderboard(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+
+    let language = msg_language(msg).await;
+    match args.current() {
+        Some("optin") => return set_ranked(ctx, msg, true).await,
+        Some("optout") => return set_ranked(ctx, msg, false).await,
+        Some(_) => return reply(ctx, msg, &tr!(language, "command.invalid_argument.title"), tr!(language, "command.invalid_argument.body", guild_prefix(msg.guild_id).await, "leaderboard")).await,
+        None => {}
+    }
+
+    let mut member_xps = Vec::new();
+    for member_id in leaderboard_members().await {
+        if guild_id.member(ctx, member_id).await.is_err() {
+            continue;
+        }
+
+        if let Some(xp_point) = member_xp(&member_id).await {
+            member_xps.push((member_id, xp_point));
+        }
+    }
+
+    if member_xps.is_empty() {
+        return reply(ctx, msg, &tr!(language, "leaderboard.empty.title"), tr!(language, "leaderboard.empty.body", guild_prefix(msg.guild_id).await)).await;
+    }
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title(tr!(language, "leaderboard.title"))
+            .colour(PRIMARY_COLOR)
+            .description(describe_ranking(&rank(member_xps), false))
+        )
+    }).await?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use serenity::framework::standard::macros::command;
+use serenity::framework::standard::{Args, CommandResult};
+use serenity::model::prelude::*;
+use serenity::prelude::*;
+
+use crate::{
+    api::new_binusmaya_api::Class,
+    consts::PRIMARY_COLOR,
+    discord::helper::{matches_course, msg_language, new_binusmaya_api_or_reply, parse_account_arg},
+};
+
+#[command]
+#[usage("[course code or name] [--account [account name]]")]
+#[example("COMP6047")]
+#[description("Get the lecturers of your active classes")]
+pub async fn lecturers(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, args) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+    let course = args.remains().map(|course| course.trim().to_string());
+    let classes: Vec<Class> = binusmaya_api
+        .get_classes()
+        .await?
+        .list
+        .into_iter()
+        .filter(|class| matches_course(class, &course))
+        .collect();
+    let language = msg_language(msg).await;
+
+    if classes.is_empty() {
+        msg.channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(tr!(language, "lecturers.title"))
+                        .description(tr!(language, "classes.no_match"))
+                        .colour(PRIMARY_COLOR)
+                })
+            })
+            .await?;
+
+        return Ok(());
+    }
+
+    for class in classes {
+        let class_details = binusmaya_api.get_class_details(class.class_id.clone()).await?;
+
+        msg.channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(format!("{} - {}", class.course_code, class.course_name))
+                        .colour(PRIMARY_COLOR)
+                        .footer(|f| f.text(format!("{} {}", class.class_Code, class.ssr_component)));
+
+                    if class_details.lecturers.is_empty() {
+                        e.description(tr!(language, "lecturers.none"));
+                    }
+
+                    for lecturer in class_details.lecturers.iter() {
+                        e.field(&lecturer.name, tr!(language, "lecturers.details", lecturer.role, lecturer.user_code), false);
+                    }
+
+                    if let Some(picture_url) = class_details.lecturers.iter().find_map(|lecturer| lecturer.picture_url.clone()) {
+                        e.thumbnail(picture_url);
+                    }
+
+                    e
+                })
+            })
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use std::borrow::Cow;
+
+use serenity::framework::standard::macros::command;
+use serenity::http::AttachmentType;
+use serenity::framework::standard::{Args, CommandResult};
+use serenity::model::{interactions::message_component::ButtonStyle, prelude::*};
+use serenity::prelude::*;
+
+use crate::{
+    api::new_binusmaya_api::Resource,
+    consts::PRIMARY_COLOR,
+    discord::{
+        archive::{archive_name, bundle_files, url_file_name, zip_files, ArchiveFile, MAX_UPLOAD_SIZE},
+        commands::new_binusmaya::session::{select_course, select_session_number},
+        helper::{button_label, msg_language, new_binusmaya_api_or_reply, parse_account_arg},
+    },
+};
+
+/// Discord allows 5 action rows of 5 buttons in a message
+const MAX_LINK_BUTTONS: usize = 25;
+
+/// Videos and external urls can't be downloaded, they're sent as link buttons
+fn is_link_only(resource: &Resource) -> bool {
+    let resource_type = format!("{} {}", resource.resource_type, resource.material_type.as_deref().unwrap_or_default()).to_lowercase();
+
+    ["video", "url", "link"].iter().any(|link_type| resource_type.contains(link_type))
+}
+
+/// Names the file after the resource and keeps the extension of the file in the url
+fn resource_file_name(name: &str, url: &str) -> String {
+    let file_name = url_file_name(url);
+
+    match file_name.rfind('.') {
+        Some(index) if !name.to_lowercase().ends_with(&file_name[index..].to_lowercase()) => format!("{}{}", name, &file_name[index..]),
+        _ => name.to_string(),
+    }
+}
+
+#[command]
+#[aliases("mat")]
+#[usage("[--account [account name]]")]
+#[description("Download the file resources of a session as a zip, videos and external links are sent as buttons")]
+pub async fn materials(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, _) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+
+    let (mci, class_id) = match select_course(ctx, msg, &binusmaya_api).await? {
+        Some(selection) => selection,
+        None => return Ok(()),
+    };
+    let class_details = binusmaya_api.get_class_details(class_id).await?;
+    let session_num = match select_session_number(ctx, msg, &mci, &class_details).await? {
+        Some(session_num) => session_num,
+        None => return Ok(()),
+    };
+    let session_details = binusmaya_api.get_resource(class_details.sessions[session_num - 1].id.clone()).await?;
+
+    msg.channel_id.broadcast_typing(&ctx.http).await?;
+
+    let mut files: Vec<ArchiveFile> = Vec::new();
+    let mut links: Vec<(String, String)> = Vec::new();
+    for resource in session_details.resources.list.iter() {
+        let url = match &resource.url {
+            Some(url) => url,
+            None => continue,
+        };
+
+        if is_link_only(resource) {
+            links.push((resource.name.clone(), url.clone()));
+            continue;
+        }
+
+        match binusmaya_api.download_resource(url, MAX_UPLOAD_SIZE).await {
+            Ok(Some((content_type, content))) if !content_type.as_deref().is_some_and(|content_type| content_type.starts_with("text/html")) => {
+                files.push((resource_file_name(&resource.name, url), content));
+            }
+            Ok(_) => links.push((resource.name.clone(), url.clone())),
+            Err(e) => {
+                eprintln!("Failed to download resource {}: {:?}", resource.id, e);
+                links.push((resource.name.clone(), url.clone()));
+            }
+        }
+    }
+
+    let language = msg_language(msg).await;
+    let title = format!("{} {} {}", class_details.course_code, tr!(language, "session.session"), session_details.session_number);
+    let bundles = bundle_files(files);
+    for (i, bundle) in bundles.iter().enumerate() {
+        let archive = zip_files(bundle)?;
+        let attachment = AttachmentType::Bytes {
+            data: Cow::from(archive),
+            filename: archive_name(&title, i + 1, bundles.len()),
+        };
+
+        msg.channel_id.send_files(&ctx.http, vec![attachment], |m| m.content(format!("**{}** - {}", title, session_details.topic))).await?;
+    }
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| {
+            e.title(tr!(language, "session.title", session_details.topic, session_details.session_number))
+                .colour(PRIMARY_COLOR);
+
+            if bundles.is_empty() && links.is_empty() {
+                e.description(tr!(language, "materials.none"));
+            } else {
+                e.description(tr!(language, "materials.summary", bundles.iter().map(|bundle| bundle.len()).sum::<usize>(), links.len()));
+            }
+
+            e
+        });
+
+        if !links.is_empty() {
+            m.components(|c| {
+                for row in links.iter().take(MAX_LINK_BUTTONS).collect::<Vec<_>>().chunks(5) {
+                    c.create_action_row(|ar| {
+                        for (name, url) in row {
+                            ar.create_button(|b| b
+                                .label(button_label(name))
+                                .style(ButtonStyle::Link)
+                                .url(url)
+                            );
+                        }
+
+                        ar
+                    });
+                }
+
+                c
+            });
+        }
+
+        m
+    }).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_file_name_test() {
+        assert_eq!(resource_file_name("Week 1 Slides", "https://example.com/files/week1.pptx?sv=2020&sig=x"), "Week 1 Slides.pptx");
+        assert_eq!(resource_file_name("Notes.pdf", "https://example.com/notes.PDF"), "Notes.pdf");
+        assert_eq!(resource_file_name("Reading", "https://example.com/reading"), "Reading");
+        assert_eq!(resource_file_name("Website", "https://example.com"), "Website");
+    }
+}
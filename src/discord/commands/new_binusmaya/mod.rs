@@ -1,6 +1,13 @@
 pub mod announcement;
 pub mod classes;
+pub mod classmates;
+pub mod leaderboard;
+pub mod lecturers;
+pub mod materials;
 pub mod session;
 pub mod ongoing;
+pub mod progress;
 pub mod schedule;
+pub mod subscribe;
 pub mod upcoming;
+pub mod xp;
\ No newline at end of file
@@ -1,85 +1,31 @@
-use std::ops::Add;
-
-use chrono::Duration;
+use chrono::Local;
 use serenity::{
-    framework::standard::{macros::command, CommandResult},
+    framework::standard::{macros::command, Args, CommandResult},
     model::prelude::*,
     prelude::*,
 };
 
-use crate::{
-    api::new_binusmaya_api::NewBinusmayaAPI,
-    consts::{PRIMARY_COLOR, NEWBINUSMAYA_USER_DATA},
-};
+use crate::discord::{helper::{msg_language, new_binusmaya_api_or_reply, parse_account_arg, Paginator}, render};
 
 #[command]
 #[description("Get ongoing classes")]
-async fn ongoing(ctx: &Context, msg: &Message) -> CommandResult {
-    let user_data = NEWBINUSMAYA_USER_DATA.clone();
+#[usage("[--account [account name]]")]
+async fn ongoing(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, _) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+    let ongoing_sessions = binusmaya_api
+        .get_ongoing_sessions()
+        .await
+        .expect("ongoing session error")
+        .data;
+    let language = msg_language(msg).await;
 
-    if user_data.lock().await.contains_key(msg.author.id.as_u64()) {
-        let jwt_exp = user_data
-            .lock()
-            .await
-            .get(msg.author.id.as_u64())
-            .unwrap()
-            .last_registered
-            .add(Duration::weeks(52));
-        let now = chrono::offset::Local::now();
+    Paginator::new(render::ongoing_classes(&ongoing_sessions, language, Local::now().naive_local()), language)
+        .send(ctx, msg, &tr!(language, "ongoing.title"))
+        .await?;
 
-        if jwt_exp > now {
-            let binusmaya_api = NewBinusmayaAPI {
-                token: user_data
-                    .lock()
-                    .await
-                    .get(msg.author.id.as_u64())
-                    .unwrap()
-                    .auth
-                    .clone(),
-            };
-            let ongoing_sessions = binusmaya_api
-                .get_ongoing_sessions()
-                .await
-                .expect("ongoing session error")
-                .data;
-            msg.channel_id
-                .send_message(&ctx.http, |m| {
-                    m.embed(|e| {
-                        e.title("Ongoing Sessions")
-                            .description(format!(
-                                "**{} Ongoing Session(s)**\n{}",
-                                ongoing_sessions.ongoing_classes.len(),
-                                ongoing_sessions
-                            ))
-                            .colour(PRIMARY_COLOR)
-                    })
-                })
-                .await?;
-        } else {
-            msg.channel_id
-                .send_message(&ctx.http, |m| {
-                    m.embed(|e| {
-                        e.colour(PRIMARY_COLOR).field(
-                            "Your bearer token has expired",
-                            "please re-register using `=add` command",
-                            false,
-                        )
-                    })
-                })
-                .await?;
-        }
-    } else {
-        msg.channel_id
-            .send_message(&ctx.http, |m| {
-                m.embed(|e| {
-                    e.colour(PRIMARY_COLOR).field(
-                        "You're not registered",
-                        "please register first using `=register` command",
-                        false,
-                    )
-                })
-            })
-            .await?;
-    }
     Ok(())
 }
@@ -0,0 +1,141 @@
+use chrono::{Local, NaiveDateTime};
+use serenity::framework::standard::macros::command;
+use serenity::framework::standard::{Args, CommandResult};
+use serenity::model::prelude::*;
+use serenity::prelude::*;
+
+use crate::{
+    api::new_binusmaya_api::{Class, NewBinusmayaAPI},
+    discord::{helper::{matches_course, msg_language, new_binusmaya_api_or_reply, parse_account_arg, Paginator}, render},
+    locale::Language,
+};
+
+const PROGRESS_BAR_WIDTH: usize = 10;
+/// How many unfinished resources are listed per course, the courses are split into pages so they fit in an embed
+const MAX_LISTED_RESOURCES: usize = 5;
+
+/// The resource completion of a course, counted over every session of the class
+#[derive(Default)]
+struct CourseProgress {
+    completed: u32,
+    in_progress: u32,
+    not_started: u32,
+    /// Session number, name and session link of the unfinished resources of the sessions that already started
+    unfinished: Vec<(u8, String, String)>,
+}
+
+impl CourseProgress {
+    fn total(&self) -> u32 {
+        self.completed + self.in_progress + self.not_started
+    }
+}
+
+fn progress_bar(completed: u32, total: u32) -> String {
+    let filled = if total == 0 { 0 } else { completed as usize * PROGRESS_BAR_WIDTH / total as usize };
+
+    format!("{}{}", "█".repeat(filled), "░".repeat(PROGRESS_BAR_WIDTH - filled))
+}
+
+fn percentage(completed: u32, total: u32) -> u32 {
+    (completed * 100).checked_div(total).unwrap_or(100)
+}
+
+async fn course_progress(binusmaya_api: &NewBinusmayaAPI, class: &Class) -> Result<CourseProgress, reqwest::Error> {
+    let class_details = binusmaya_api.get_class_details(class.class_id.clone()).await?;
+    let now = Local::now().naive_local();
+    let mut progress = CourseProgress::default();
+
+    for session in class_details.sessions {
+        let session_details = binusmaya_api.get_resource(session.id.clone()).await?;
+        progress.completed += session_details.class_session_progress.completed as u32;
+        progress.in_progress += session_details.class_session_progress.in_progress as u32;
+        progress.not_started += session_details.class_session_progress.not_started as u32;
+
+        let has_started = NaiveDateTime::parse_from_str(&session_details.date_start, "%FT%X")
+            .is_ok_and(|date_start| date_start <= now);
+        if !has_started {
+            continue;
+        }
+
+        let session_url = render::session_url(&class.class_id, &session.id);
+        for resource in session_details.resources.list.iter().filter(|resource| !resource.is_completed()) {
+            progress.unfinished.push((session_details.session_number, resource.name.clone(), session_url.clone()));
+        }
+    }
+
+    Ok(progress)
+}
+
+fn describe_progress(progress: &CourseProgress, language: Language) -> String {
+    let mut content = format!(
+        "`{}` **{}%** {}\n",
+        progress_bar(progress.completed, progress.total()),
+        percentage(progress.completed, progress.total()),
+        tr!(language, "progress.summary", progress.completed, progress.total(), progress.in_progress)
+    );
+
+    for (session_number, name, url) in progress.unfinished.iter().take(MAX_LISTED_RESOURCES) {
+        content.push_str(&format!("> [{} {} - {}]({})\n", tr!(language, "session.session"), session_number, name, url));
+    }
+
+    if progress.unfinished.len() > MAX_LISTED_RESOURCES {
+        content.push_str(&format!("> {}\n", tr!(language, "progress.more", progress.unfinished.len() - MAX_LISTED_RESOURCES)));
+    }
+
+    content
+}
+
+#[command]
+#[usage("[course code or name] [--account [account name]]")]
+#[example("COMP6047")]
+#[description("Get the resource completion of your active classes and the unfinished resources of past sessions")]
+pub async fn progress(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, args) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+    let course = args.remains().map(|course| course.trim().to_string());
+    let classes: Vec<Class> = binusmaya_api
+        .get_classes()
+        .await?
+        .list
+        .into_iter()
+        .filter(|class| matches_course(class, &course))
+        .collect();
+
+    msg.channel_id.broadcast_typing(&ctx.http).await?;
+
+    let mut course_progresses = Vec::new();
+    for class in classes.iter() {
+        course_progresses.push((class, course_progress(&binusmaya_api, class).await?));
+    }
+
+    let language = msg_language(msg).await;
+    let mut content = format!("*{}*\n\n", tr!(language, "progress.note"));
+    if course_progresses.is_empty() {
+        content.push_str(&tr!(language, "classes.no_match"));
+    }
+
+    for (class, progress) in course_progresses.iter() {
+        content.push_str(&format!("**{} - {} ({})**\n{}\n", class.course_code, class.course_name, class.ssr_component, describe_progress(progress, language)));
+    }
+
+    Paginator::new(content, language).send(ctx, msg, &tr!(language, "progress.title")).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_bar_test() {
+        assert_eq!(progress_bar(0, 0), "░░░░░░░░░░");
+        assert_eq!(progress_bar(5, 10), "█████░░░░░");
+        assert_eq!(progress_bar(7, 7), "██████████");
+        assert_eq!(percentage(1, 3), 33);
+        assert_eq!(percentage(0, 0), 100);
+    }
+}
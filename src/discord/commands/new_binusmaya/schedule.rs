@@ -1,194 +1,241 @@
-use chrono::{Duration, NaiveDate};
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
 use futures::StreamExt;
 use serenity::{
+    builder::{CreateActionRow, CreateComponents, CreateEmbed},
     framework::standard::{macros::command, Args, CommandResult},
-    model::prelude::*,
+    model::{interactions::message_component::ButtonStyle, prelude::*},
     prelude::*,
 };
-use std::{ops::Add, str::FromStr};
 
 use crate::{
-    api::new_binusmaya_api::NewBinusmayaAPI,
-    consts::{PRIMARY_COLOR, NEWBINUSMAYA_USER_DATA},
-    discord::helper::Nav,
+    api::new_binusmaya_api::Schedule,
+    consts::PRIMARY_COLOR,
+    discord::{helper::{disable_components, interaction_timeout, is_invoker, msg_language, new_binusmaya_api_or_reply, parse_account_arg, reply, Paginator}, render},
+    locale::{day_names, format_date, Language},
 };
 
-// async fn send_interactive_msg(ctx: &Context, msg: &Message, date: NaiveDate)
+/// The buttons that move the schedule to another date
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DateNav {
+    Previous,
+    Today,
+    Next,
+    NextWeek,
+}
+
+impl DateNav {
+    const ALL: [DateNav; 4] = [DateNav::Previous, DateNav::Today, DateNav::Next, DateNav::NextWeek];
+
+    fn custom_id(&self) -> &'static str {
+        match self {
+            Self::Previous => "schedule_previous",
+            Self::Today => "schedule_today",
+            Self::Next => "schedule_next",
+            Self::NextWeek => "schedule_next_week",
+        }
+    }
+
+    fn label(&self, language: Language) -> String {
+        match self {
+            Self::Previous => "<".to_string(),
+            Self::Today => tr!(language, "schedule.today"),
+            Self::Next => ">".to_string(),
+            Self::NextWeek => tr!(language, "schedule.next_week"),
+        }
+    }
+
+    fn from_custom_id(custom_id: &str) -> Option<Self> {
+        Self::ALL.iter().find(|nav| nav.custom_id().eq(custom_id)).copied()
+    }
+
+    fn apply(&self, date: NaiveDate, today: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Previous => date - Duration::days(1),
+            Self::Today => today,
+            Self::Next => date + Duration::days(1),
+            Self::NextWeek => date + Duration::days(7),
+        }
+    }
+
+    fn action_row(language: Language) -> CreateActionRow {
+        let mut ar = CreateActionRow::default();
+        for nav in Self::ALL.iter() {
+            ar.create_button(|b| b
+                .custom_id(nav.custom_id())
+                .label(nav.label(language))
+                .style(if let Self::Today = nav { ButtonStyle::Secondary } else { ButtonStyle::Primary })
+            );
+        }
+
+        ar
+    }
+}
+
+/// Parses `YYYY-MM-DD`, `today`, `tomorrow`, `yesterday`, a day name like `monday` for its next occurrence
+/// (today if it's that day) and `next week` for the monday of next week, in English or Indonesian.
+/// Returns the input back if it isn't a date
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let input = input.trim().to_lowercase();
+
+    match input.as_str() {
+        "" | "today" | "hari ini" => return Ok(today),
+        "tomorrow" | "besok" => return Ok(today + Duration::days(1)),
+        "yesterday" | "kemarin" => return Ok(today - Duration::days(1)),
+        "next week" | "minggu depan" => return Ok(today + Duration::days(7 - today.weekday().num_days_from_monday() as i64)),
+        _ => {}
+    }
+
+    let weekday = input.parse::<Weekday>().ok().or_else(|| {
+        day_names(Language::Indonesian)
+            .iter()
+            .position(|day| day.eq_ignore_ascii_case(&input))
+            .and_then(|i| day_names(Language::English)[i].parse::<Weekday>().ok())
+    });
+    if let Some(weekday) = weekday {
+        let days_ahead = (7 + weekday.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64) % 7;
+        return Ok(today + Duration::days(days_ahead));
+    }
+
+    NaiveDate::parse_from_str(&input, "%Y-%m-%d").map_err(|_| input)
+}
+
+fn schedule_title(date: NaiveDate, language: Language) -> String {
+    tr!(language, "schedule.title", format_date(language, date))
+}
+
+/// A holiday has no schedule to paginate
+fn schedule_paginator(schedule: Option<Schedule>, language: Language) -> Option<Paginator> {
+    schedule.map(|schedule| Paginator::new(render::schedule(&schedule, language), language))
+}
+
+fn schedule_embed<'a>(e: &'a mut CreateEmbed, title: &str, paginator: &Option<Paginator>, language: Language) -> &'a mut CreateEmbed {
+    e.title(title).colour(PRIMARY_COLOR);
+
+    match paginator {
+        Some(paginator) => {
+            e.description(paginator.page());
+            if paginator.is_paginated() {
+                e.footer(|f| f.text(paginator.footer()));
+            }
+        }
+        None => {
+            e.field(tr!(language, "schedule.holiday.title"), tr!(language, "schedule.holiday.body"), true);
+        }
+    }
+
+    e
+}
+
+fn schedule_components<'a>(c: &'a mut CreateComponents, paginator: &Option<Paginator>, language: Language) -> &'a mut CreateComponents {
+    c.add_action_row(DateNav::action_row(language));
+
+    match paginator {
+        Some(paginator) => paginator.add_action_row(c),
+        None => c,
+    }
+}
 
 #[command]
-#[num_args(1)]
-#[description("Get the schedule of the given date")]
-#[usage("[YYYY-MM-DD]")]
+#[description("Get the schedule of the given date, today if no date is given")]
+#[usage("[YYYY-MM-DD | today | tomorrow | yesterday | monday | next week] [--account [account name]]")]
+#[example("besok")]
 #[example("2022-01-05")]
-async fn schedule(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let date = args.single::<String>().unwrap();
-    let user_data = NEWBINUSMAYA_USER_DATA.clone();
-
-    if user_data.lock().await.contains_key(msg.author.id.as_u64()) {
-        let jwt_exp = user_data
-            .lock()
-            .await
-            .get(msg.author.id.as_u64())
-            .unwrap()
-            .last_registered
-            .add(Duration::weeks(52));
-        let now = chrono::offset::Local::now();
-        if jwt_exp > now {
-            let mut parsed_date = NaiveDate::parse_from_str(&date, "%Y-%-m-%-d").unwrap();
-            let binusmaya_api = NewBinusmayaAPI {
-                token: user_data
-                    .lock()
-                    .await
-                    .get(msg.author.id.as_u64())
-                    .unwrap()
-                    .auth
-                    .clone(),
+#[example("tomorrow")]
+#[example("next week")]
+async fn schedule(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, args) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let today = Local::now().naive_local().date();
+    let language = msg_language(msg).await;
+    let mut date = match parse_date(args.remains().unwrap_or_default(), today) {
+        Ok(date) => date,
+        Err(input) => return reply(ctx, msg, &tr!(language, "schedule.invalid_date.title"), tr!(language, "schedule.invalid_date.body", input)).await,
+    };
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+    let mut paginator = schedule_paginator(binusmaya_api.get_schedule(&date).await?, language);
+
+    let mut mesg = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| schedule_embed(e, &schedule_title(date, language), &paginator, language));
+            m.components(|c| schedule_components(c, &paginator, language))
+        })
+        .await?;
+
+    let mut cib = mesg
+        .await_component_interactions(&ctx)
+        .timeout(interaction_timeout(msg.guild_id).await)
+        .await;
+    while let Some(mci) = cib.next().await {
+        if !is_invoker(ctx, msg, &mci).await? {
+            continue;
+        }
+
+        let turned = paginator.as_mut().is_some_and(|paginator| paginator.turn(&mci.data.custom_id));
+
+        if !turned {
+            let nav = match DateNav::from_custom_id(&mci.data.custom_id) {
+                Some(nav) => nav,
+                None => continue,
             };
-            let mut schedule = binusmaya_api.get_schedule(&parsed_date).await?;
-            let mesg: Message;
-
-            if let Some(class) = schedule {
-                mesg = msg
-                    .channel_id
-                    .send_message(&ctx.http, |m| {
-                        m.embed(|e| {
-                            e.title(format!("Schedule for {}", date.clone()))
-                                .description(format!(
-                                    "**{} Session(s)**\n{}",
-                                    class.schedule.len(),
-                                    class
-                                ))
-                                .colour(PRIMARY_COLOR)
-                        });
-                        m.components(|c| c.add_action_row(Nav::action_row()))
-                    })
-                    .await?;
-            } else {
-                mesg = msg
-                    .channel_id
-                    .send_message(&ctx.http, |m| {
-                        m.embed(|e| {
-                            e.title(format!("Schedule for {}", date.clone()))
-                                .colour(PRIMARY_COLOR)
-                                .field("Holiday!", "No classes/sessions for today", true)
-                        });
-                        m.components(|c| c.add_action_row(Nav::action_row()))
-                    })
-                    .await?;
-            }
 
-            let mut cib = mesg
-                .await_component_interactions(&ctx)
-                .await;
-            while let Some(mci) = cib.next().await {
-                parsed_date = parsed_date.pred();
-                let nav = Nav::from_str(&mci.data.custom_id).unwrap();
-                match nav {
-                    Nav::Previous => {
-                        schedule = binusmaya_api.get_schedule(&parsed_date).await?;
-                        if let Some(class) = schedule {
-                            mci.create_interaction_response(&ctx, |r| {
-                                r.kind(InteractionResponseType::UpdateMessage);
-                                r.interaction_response_data(|m| {
-                                    m.create_embed(|e| {
-                                        e.title(format!("Schedule for {}", parsed_date.to_string()))
-                                            .description(format!(
-                                                "**{} Session(s)**\n{}",
-                                                class.schedule.len(),
-                                                class
-                                            ))
-                                            .colour(PRIMARY_COLOR)
-                                    });
-                                    m.components(|c| c.add_action_row(Nav::action_row()))
-                                })
-                            })
-                            .await?;
-                        } else {
-                            mci.create_interaction_response(&ctx, |r| {
-                                r.kind(InteractionResponseType::UpdateMessage);
-                                r.interaction_response_data(|m| {
-                                    m.create_embed(|e| {
-                                        e.title(format!("Schedule for {}", parsed_date.to_string()))
-                                            .colour(PRIMARY_COLOR)
-                                            .field(
-                                                "Holiday!",
-                                                "No classes/sessions for today",
-                                                true,
-                                            )
-                                    });
-                                    m.components(|c| c.add_action_row(Nav::action_row()))
-                                })
-                            })
-                            .await?;
-                        }
-                    }
-                    Nav::Next => {
-                        parsed_date = parsed_date.succ().succ();
-                        schedule = binusmaya_api.get_schedule(&parsed_date).await?;
-                        if let Some(class) = schedule {
-                            mci.create_interaction_response(&ctx, |r| {
-                                r.kind(InteractionResponseType::UpdateMessage);
-                                r.interaction_response_data(|m| {
-                                    m.create_embed(|e| {
-                                        e.title(format!("Schedule for {}", parsed_date.to_string()))
-                                            .description(format!(
-                                                "**{} Session(s)**\n{}",
-                                                class.schedule.len(),
-                                                class
-                                            ))
-                                            .colour(PRIMARY_COLOR)
-                                    });
-                                    m.components(|c| c.add_action_row(Nav::action_row()))
-                                })
-                            })
-                            .await?;
-                        } else {
-                            mci.create_interaction_response(&ctx, |r| {
-                                r.kind(InteractionResponseType::UpdateMessage);
-                                r.interaction_response_data(|m| {
-                                    m.create_embed(|e| {
-                                        e.title(format!("Schedule for {}", parsed_date.to_string()))
-                                            .colour(PRIMARY_COLOR)
-                                            .field(
-                                                "Holiday!",
-                                                "No classes/sessions for today",
-                                                true,
-                                            )
-                                    });
-                                    m.components(|c| c.add_action_row(Nav::action_row()))
-                                })
-                            })
-                            .await?;
-                        }
-                    }
-                }
-            }
-        } else {
-            msg.channel_id
-                .send_message(&ctx.http, |m| {
-                    m.embed(|e| {
-                        e.colour(PRIMARY_COLOR).field(
-                            "Your bearer token has expired",
-                            "please re-register using `=add` command",
-                            false,
-                        )
-                    })
-                })
-                .await?;
+            date = nav.apply(date, Local::now().naive_local().date());
+            paginator = schedule_paginator(binusmaya_api.get_schedule(&date).await?, language);
         }
-    } else {
-        msg.channel_id
-            .send_message(&ctx.http, |m| {
-                m.embed(|e| {
-                    e.colour(PRIMARY_COLOR).field(
-                        "You're not registered",
-                        "please register first using `=register` command",
-                        false,
-                    )
-                })
+
+        mci.create_interaction_response(&ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage);
+            r.interaction_response_data(|m| {
+                m.create_embed(|e| schedule_embed(e, &schedule_title(date, language), &paginator, language));
+                m.components(|c| schedule_components(c, &paginator, language))
             })
-            .await?;
+        })
+        .await?;
     }
 
+    disable_components(ctx, &mut mesg).await?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_date_test() {
+        // a wednesday
+        let today = NaiveDate::from_ymd_opt(2022, 1, 5).unwrap();
+
+        assert_eq!(parse_date("", today), Ok(today));
+        assert_eq!(parse_date("Today", today), Ok(today));
+        assert_eq!(parse_date("tomorrow", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 6).unwrap()));
+        assert_eq!(parse_date("yesterday", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 4).unwrap()));
+        assert_eq!(parse_date("wednesday", today), Ok(today));
+        assert_eq!(parse_date("monday", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 10).unwrap()));
+        assert_eq!(parse_date("fri", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap()));
+        assert_eq!(parse_date("next week", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 10).unwrap()));
+        assert_eq!(parse_date("2022-2-1", today), Ok(NaiveDate::from_ymd_opt(2022, 2, 1).unwrap()));
+        assert!(parse_date("2022-13-01", today).is_err());
+        assert_eq!(parse_date("besok", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 6).unwrap()));
+        assert_eq!(parse_date("Jumat", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap()));
+        assert_eq!(parse_date("minggu", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 9).unwrap()));
+        assert_eq!(parse_date("minggu depan", today), Ok(NaiveDate::from_ymd_opt(2022, 1, 10).unwrap()));
+        assert_eq!(parse_date("someday", today), Err("someday".to_string()));
+    }
+
+    #[test]
+    fn date_nav_test() {
+        let today = NaiveDate::from_ymd_opt(2022, 1, 5).unwrap();
+        let date = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+
+        assert_eq!(DateNav::from_custom_id("schedule_next"), Some(DateNav::Next));
+        assert_eq!(DateNav::from_custom_id(">"), None);
+        assert_eq!(DateNav::Previous.apply(date, today), NaiveDate::from_ymd_opt(2022, 1, 30).unwrap());
+        assert_eq!(DateNav::Next.apply(date, today), NaiveDate::from_ymd_opt(2022, 2, 1).unwrap());
+        assert_eq!(DateNav::NextWeek.apply(date, today), NaiveDate::from_ymd_opt(2022, 2, 7).unwrap());
+        assert_eq!(DateNav::Today.apply(date, today), today);
+    }
+}
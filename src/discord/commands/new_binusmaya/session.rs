@@ -1,17 +1,18 @@
-use std::ops::Add;
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
 use chrono::Duration;
 use futures::StreamExt;
-use serenity::builder::{CreateSelectMenuOption, CreateActionRow};
+use serenity::builder::{CreateSelectMenuOption, CreateActionRow, CreateEmbed};
 use serenity::framework::standard::macros::command;
-use serenity::framework::standard::CommandError;
-use serenity::model::prelude::*;
+use serenity::framework::standard::{Args, CommandError};
+use serenity::model::{interactions::message_component::MessageComponentInteraction, prelude::*};
 use serenity::prelude::*;
 
-use crate::api::new_binusmaya_api::NewBinusmayaAPI;
-use crate::consts::{NEWBINUSMAYA_USER_DATA, PRIMARY_COLOR};
-use crate::discord::helper::{Nav, select_menu};
+use crate::api::new_binusmaya_api::{ClassDetails, NewBinusmayaAPI, SessionDetails};
+use crate::consts::PRIMARY_COLOR;
+use crate::discord::render;
+use crate::discord::helper::{await_invoker_interaction, disable_components, interaction_timeout, is_invoker, msg_language, new_binusmaya_api_or_reply, parse_account_arg, Nav, Paginator, select_menu};
+use crate::locale::Language;
 
 async fn academic_period_menu_options(binusmaya_api: &NewBinusmayaAPI) -> Vec<CreateSelectMenuOption> {
     let mut vec_opt: Vec<CreateSelectMenuOption> = Vec::new();
@@ -53,212 +54,192 @@ async fn course_menu_options(binusmaya_api: &NewBinusmayaAPI, academic_period: &
 	vec_opt
 }
 
+/// Lets the user pick a course with the academic period, class component and course select menus,
+/// returns the interaction of the course menu and the class id of the course
+pub async fn select_course(ctx: &Context, msg: &Message, binusmaya_api: &NewBinusmayaAPI) -> Result<Option<(Arc<MessageComponentInteraction>, String)>, CommandError> {
+	let language = msg_language(msg).await;
+	let academic_period_select_menu = select_menu(academic_period_menu_options(binusmaya_api).await, language).await;
+	let mut m = msg.channel_id.send_message(&ctx.http, |m| {
+        m.content(tr!(language, "menu.academic_period"));
+        m.components(|c| c.add_action_row({
+            let mut ar = CreateActionRow::default();
+            ar.add_select_menu(academic_period_select_menu);
+
+            ar
+        }))
+    }).await?;
+
+	let mci = match await_invoker_interaction(ctx, msg, &mut m).await? {
+		Some(mci) => mci,
+		None => return Ok(None),
+	};
+	let academic_period = mci.data.values.first().unwrap();
+
+	let class_component_select_menu = select_menu(class_component_menu_options(binusmaya_api, academic_period).await, language).await;
+	
+	mci.create_interaction_response(&ctx, |r| {
+		r.kind(InteractionResponseType::UpdateMessage);
+		r.interaction_response_data(|d| {
+			d.content(tr!(language, "menu.class_component"));
+			d.components(|c| c.add_action_row({
+				let mut ar = CreateActionRow::default();
+				ar.add_select_menu(class_component_select_menu);
+
+				ar
+			}))
+		})
+	}).await?;
+
+	let mci = match await_invoker_interaction(ctx, msg, &mut m).await? {
+		Some(mci) => mci,
+		None => return Ok(None),
+	};
+	let class_component = mci.data.values.first().unwrap();
+
+	let course_select_menu = select_menu(course_menu_options(binusmaya_api, academic_period, class_component).await, language).await;
+
+	mci.create_interaction_response(&ctx, |r| {
+		r.kind(InteractionResponseType::UpdateMessage);
+		r.interaction_response_data(|d| {
+			d.content(tr!(language, "menu.course"));
+			d.components(|c| c.add_action_row({
+				let mut ar = CreateActionRow::default();
+				ar.add_select_menu(course_select_menu);
+
+				ar
+			}))
+		})
+	}).await?;
+
+	let mci = match await_invoker_interaction(ctx, msg, &mut m).await? {
+		Some(mci) => mci,
+		None => return Ok(None),
+	};
+	let class_id = mci.data.values.first().unwrap().to_string();
+
+	Ok(Some((mci, class_id)))
+}
+
+/// Asks the user for a session number of the class in response to the course menu interaction
+pub async fn select_session_number(ctx: &Context, msg: &Message, mci: &MessageComponentInteraction, class_details: &ClassDetails) -> Result<Option<usize>, CommandError> {
+	let language = msg_language(msg).await;
+	if class_details.sessions.is_empty() {
+		mci.create_interaction_response(&ctx, |r| {
+			r.kind(InteractionResponseType::ChannelMessageWithSource);
+			r.interaction_response_data(|d| d.create_embed(|e| e.description(tr!(language, "session.none")).colour(PRIMARY_COLOR)))
+		}).await?;
+
+		return Ok(None);
+	}
+
+	mci.create_interaction_response(&ctx, |r| {
+		r.kind(InteractionResponseType::ChannelMessageWithSource);
+		r.interaction_response_data(|d| {
+			d.create_embed(|e| e
+				.field(tr!(language, "session.choose_number.title"), tr!(language, "session.choose_number.body", class_details.sessions.len()), false)
+				.footer(|f| f.text(tr!(language, "session.choose_number.footer")))
+				.colour(PRIMARY_COLOR)
+			)
+		})
+	}).await?;
+
+	let reply = match msg.author.await_reply(&ctx).timeout(Duration::seconds(30).to_std().unwrap()).await {
+		Some(reply) => reply,
+		None => return Ok(None),
+	};
+
+	if reply.content.eq("cancel") {
+		msg.react(&ctx, '👍').await?;
+		return Ok(None);
+	}
+
+	let session_num: usize = reply.content.parse().unwrap_or(1);
+
+	Ok(Some(session_num.clamp(1, class_details.sessions.len())))
+}
+
+fn session_paginator(session_details: &SessionDetails, language: Language) -> Paginator {
+	Paginator::new(render::session_details(session_details, language), language)
+}
+
+fn session_embed<'a>(e: &'a mut CreateEmbed, session_details: &SessionDetails, paginator: &Paginator, language: Language) -> &'a mut CreateEmbed {
+	e.title(tr!(language, "session.title", session_details.topic, session_details.session_number))
+		.description(paginator.page())
+		.colour(PRIMARY_COLOR)
+}
+
+fn session_footer(session_num: usize, sessions: usize, paginator: &Paginator, language: Language) -> String {
+	if paginator.is_paginated() {
+		format!("{}, {}", tr!(language, "session.footer", session_num, sessions), paginator.footer())
+	} else {
+		tr!(language, "session.footer", session_num, sessions)
+	}
+}
+
 #[command]
 #[description("Get session details")]
 #[aliases("resource", "res")]
-async fn session(ctx: &Context, msg: &Message) -> Result<(), CommandError> {
-    let user_data = NEWBINUSMAYA_USER_DATA.clone();
-
-    if user_data.lock().await.contains_key(msg.author.id.as_u64()) {
-        let jwt_exp = user_data
-            .lock()
-            .await
-            .get(msg.author.id.as_u64())
-            .unwrap()
-            .last_registered
-            .add(Duration::weeks(52));
-        let now = chrono::offset::Local::now();
-        if jwt_exp > now {
-			let binusmaya_api = NewBinusmayaAPI {
-                token: user_data
-                    .lock()
-                    .await
-                    .get(msg.author.id.as_u64())
-                    .unwrap()
-                    .auth
-                    .clone(),
-            };
-
-			let academic_period_select_menu = select_menu(academic_period_menu_options(&binusmaya_api).await).await;
-			let m = msg.channel_id.send_message(&ctx.http, |m| {
-                m.content("Choose academic period");
-                m.components(|c| c.add_action_row({
-                    let mut ar = CreateActionRow::default();
-                    ar.add_select_menu(academic_period_select_menu);
-
-                    ar
-                }))
-            }).await?;
-
-			let mci = m.await_component_interaction(&ctx).await.unwrap();
-			let academic_period = mci.data.values.get(0).unwrap();
-
-			let class_component_select_menu = select_menu(class_component_menu_options(&binusmaya_api, academic_period).await).await;
-			
-			mci.create_interaction_response(&ctx, |r| {
-				r.kind(InteractionResponseType::UpdateMessage);
-				r.interaction_response_data(|d| {
-					d.content("Choose class component");
-					d.components(|c| c.add_action_row({
-						let mut ar = CreateActionRow::default();
-						ar.add_select_menu(class_component_select_menu);
-
-						ar
-					}))
-				})
-			}).await?;
+#[usage("[--account [account name]]")]
+async fn session(ctx: &Context, msg: &Message, args: Args) -> Result<(), CommandError> {
+    let (account, _) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+
+	let (mci, class_id) = match select_course(ctx, msg, &binusmaya_api).await? {
+		Some(selection) => selection,
+		None => return Ok(()),
+	};
+	let class_details = binusmaya_api.get_class_details(class_id.clone()).await?;
+
+	if let Some(mut session_num) = select_session_number(ctx, msg, &mci, &class_details).await? {
+		let language = msg_language(msg).await;
+		let mut session_id = class_details.sessions[session_num - 1].id.clone();
+		let mut session_details = binusmaya_api.get_resource(session_id.clone()).await?;
+		let mut paginator = session_paginator(&session_details, language);
+
+		let mut mesg = msg.channel_id.send_message(&ctx.http, |m| {
+			m.embed(|e| session_embed(e, &session_details, &paginator, language)
+				.url(render::session_url(&class_id, &session_id))
+				.footer(|f| f.text(session_footer(session_num, class_details.sessions.len(), &paginator, language)))
+			);
+			m.components(|c| paginator.add_action_row(c.add_action_row(Nav::action_row())))
+		}).await?;
+
+		let mut cib = mesg.await_component_interactions(&ctx).timeout(interaction_timeout(msg.guild_id).await).await;
+		while let Some(mci) = cib.next().await {
+			if !is_invoker(ctx, msg, &mci).await? {
+				continue;
+			}
 
-			let mci = m.await_component_interaction(&ctx).await.unwrap();
-			let class_component = mci.data.values.get(0).unwrap();
+			if !paginator.turn(&mci.data.custom_id) {
+				session_num = match Nav::from_str(&mci.data.custom_id).unwrap() {
+					Nav::Previous => (session_num - 1).max(1),
+					Nav::Next => (session_num + 1).min(class_details.sessions.len()),
+				};
 
-			let course_select_menu = select_menu(course_menu_options(&binusmaya_api, academic_period, class_component).await).await;
+				session_id = class_details.sessions[session_num - 1].id.clone();
+				session_details = binusmaya_api.get_resource(session_id.clone()).await?;
+				paginator = session_paginator(&session_details, language);
+			}
 
 			mci.create_interaction_response(&ctx, |r| {
 				r.kind(InteractionResponseType::UpdateMessage);
-				r.interaction_response_data(|d| {
-					d.content("Choose course");
-					d.components(|c| c.add_action_row({
-						let mut ar = CreateActionRow::default();
-						ar.add_select_menu(course_select_menu);
-
-						ar
-					}))
-				})
-			}).await?;
-
-			let mci = m.await_component_interaction(&ctx).await.unwrap();
-			let class_id = mci.data.values.get(0).unwrap();
-			let class_details = binusmaya_api.get_class_details(class_id.to_string()).await?;
-
-			mci.create_interaction_response(&ctx, |r| {
-				r.kind(InteractionResponseType::ChannelMessageWithSource);
-				r.interaction_response_data(|d| {
-					d.create_embed(|e| e
-						.field("Choose Session Number", format!("Choose session number from 1 - {}", class_details.sessions.len()), false)
-						.footer(|f| f.text("Timeout in 30 seconds, type cancel to cancel operation"))
-						.colour(PRIMARY_COLOR)
-					)
-				})
-			}).await?;
-
-			if let Some(reply) = &msg.author.await_reply(&ctx).timeout(Duration::seconds(30).to_std().unwrap()).await {
-				if reply.content.eq("cancel") {
-					msg.react(&ctx, '👍').await?;
-					return Ok(());
-				}
-
-				let mut session_num: usize = reply.content.parse().unwrap_or(1);
-
-				if session_num > class_details.sessions.len() {
-					session_num = class_details.sessions.len();
-				} else if session_num < 1 {
-					session_num = 1;
-				}
-
-				let session_id = &class_details.sessions[session_num - 1].id;
-				let session_details = binusmaya_api.get_resource(session_id.to_string()).await?;
-
-				let mesg = msg.channel_id.send_message(&ctx.http, |m| {
-					m.embed(|e| e
-						.title(format!("{}\nSession {}", session_details.topic, session_details.session_number))
-						.description(format!("**Class Zoom Link**\n{}\n\n**Subtopics**\n{}\n**Resources**\n{}", session_details.join_url.unwrap_or("No link".to_string()), session_details.course_sub_topic, session_details.resources))
-						.colour(PRIMARY_COLOR)
-						.url(format!("https://newbinusmaya.binus.ac.id/lms/course/{}/session/{}", class_id.clone(), session_id))
-						.footer(|f| f.text(format!("session {}/{}", session_num, class_details.sessions.len())))
+				r.interaction_response_data(|m| {
+					m.create_embed(|e| session_embed(e, &session_details, &paginator, language)
+						.url(render::session_url(&class_id, &session_id))
+						.footer(|f| f.text(session_footer(session_num, class_details.sessions.len(), &paginator, language)))
 					);
-					m.components(|c| c.add_action_row(Nav::action_row()));
-					m
-				}).await?;
-
-				let mut cib = mesg.await_component_interactions(&ctx).await;
-				while let Some(mci) = cib.next().await {
-					let nav = Nav::from_str(&mci.data.custom_id).unwrap();
-					match nav {
-						Nav::Previous => {
-							session_num = if session_num > 1 {
-								session_num - 1
-							} else {
-								1
-							};
-
-							let session_id = &class_details.sessions[session_num - 1].id;
-							let session_details = binusmaya_api
-								.get_resource(session_id.to_string())
-								.await
-								.unwrap();
-
-							mci.create_interaction_response(&ctx, |r| {
-								r.kind(InteractionResponseType::UpdateMessage);
-								r.interaction_response_data(|m| {
-									m.create_embed(|e| e
-										.title(format!("{}\nSession {}", session_details.topic, session_details.session_number))
-										.description(format!("**Class Zoom Link**\n{}\n\n**Subtopics**\n{}\n**Resources**\n{}", session_details.join_url.unwrap_or("No link".to_string()), session_details.course_sub_topic, session_details.resources))
-										.colour(PRIMARY_COLOR)
-										.url(format!("https://newbinusmaya.binus.ac.id/lms/course/{}/session/{}", class_id.clone(), session_id))
-										.footer(|f| f.text(format!("session {}/{}", session_num, class_details.sessions.len())))
-									);
-									m.components(|c| c.add_action_row(Nav::action_row()))
-								})
-							}).await?;
-						}
-						Nav::Next => {
-							session_num = if session_num < class_details.sessions.len() {
-								session_num + 1
-							} else {
-								class_details.sessions.len()
-							};
-
-							let session_id = &class_details.sessions[session_num - 1].id;
-							let session_details = binusmaya_api
-								.get_resource(session_id.to_string())
-								.await
-								.unwrap();
-
-							mci.create_interaction_response(&ctx, |r| {
-								r.kind(InteractionResponseType::UpdateMessage);
-								r.interaction_response_data(|m| {
-									m.create_embed(|e| e
-										.title(format!("{}\nSession {}", session_details.topic, session_details.session_number))
-										.description(format!("**Class Zoom Link**\n{}\n\n**Subtopics**\n{}\n**Resources**\n{}", session_details.join_url.unwrap_or("No link".to_string()), session_details.course_sub_topic, session_details.resources))
-										.colour(PRIMARY_COLOR)
-										.url(format!("https://newbinusmaya.binus.ac.id/lms/course/{}/session/{}", class_id.clone(), session_id))
-										.footer(|f| f.text(format!("session {}/{}", session_num, class_details.sessions.len())))
-									);
-									m.components(|c| c.add_action_row(Nav::action_row()))
-								})
-							}).await?;
-						}
-					}
-				}
-			} else {
-				return Ok(());
-			}
-		} else {
-			msg.channel_id
-			.send_message(&ctx.http, |m| {
-				m.embed(|e| {
-					e.colour(PRIMARY_COLOR).field(
-						"Your bearer token has expired",
-						"please re-register using `=add` command",
-						false,
-					)
+					m.components(|c| paginator.add_action_row(c.add_action_row(Nav::action_row())))
 				})
-			})
-			.await?;
+			}).await?;
 		}
+
+		disable_components(ctx, &mut mesg).await?;
 	} else {
-        msg.channel_id
-            .send_message(&ctx.http, |m| {
-                m.embed(|e| {
-                    e.colour(PRIMARY_COLOR).field(
-                        "You're not registered",
-                        "please register first using `=register` command",
-                        false,
-                    )
-                })
-            })
-            .await?;
-    }
+		return Ok(());
+	}
 	
 	Ok(())
 }
\ No newline at end of file
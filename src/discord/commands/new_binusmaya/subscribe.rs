@@ -0,0 +1,192 @@
+use futures::StreamExt;
+use serenity::{
+    framework::standard::{macros::command, Args, CommandResult, Delimiter},
+    model::{
+        interactions::{message_component::ButtonStyle, InteractionApplicationCommandCallbackDataFlags},
+        prelude::*,
+    },
+    prelude::*,
+    utils::MessageBuilder,
+};
+
+use crate::{
+    consts::{CLASS_SUBSCRIPTION_FILE, PRIMARY_COLOR},
+    discord::{
+        class_subscription::{ClassSubscription, NOTICE_BEFORE_START},
+        helper::{self, active_account, active_new_binusmaya_api, guild_prefix, interaction_timeout, is_guild_admin, msg_language, overwrite_records, read_records, reply},
+    },
+};
+
+const CONSENT: &str = "subscription_consent";
+
+async fn list_subscriptions(ctx: &Context, msg: &Message, guild_id: u64) -> CommandResult {
+    let subscriptions: Vec<ClassSubscription> = read_records::<ClassSubscription>(CLASS_SUBSCRIPTION_FILE)
+        .await
+        .into_iter()
+        .filter(|subscription| subscription.guild_id == guild_id)
+        .collect();
+    let language = msg_language(msg).await;
+
+    let mut content = MessageBuilder::new();
+    if subscriptions.is_empty() {
+        content.push(tr!(language, "subscribe.none"));
+    }
+
+    for subscription in subscriptions.iter() {
+        let source_status = if subscription.source_api().await.is_some() { String::new() } else { tr!(language, "subscribe.token_expired") };
+
+        content
+            .channel(ChannelId(subscription.channel_id))
+            .push(" ")
+            .push_bold(format!("{} {}", subscription.class_code, subscription.course_name))
+            .push(tr!(language, "subscribe.data_from", subscription.class_id))
+            .mention(&UserId(subscription.source_member_id))
+            .push_line(source_status);
+    }
+
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .title(tr!(language, "subscribe.title"))
+            .colour(PRIMARY_COLOR)
+            .description(content.build())
+        )
+    }).await?;
+
+    Ok(())
+}
+
+async fn remove_subscription(ctx: &Context, msg: &Message, guild_id: u64, class_id: &str) -> CommandResult {
+    let mut subscriptions = read_records::<ClassSubscription>(CLASS_SUBSCRIPTION_FILE).await;
+    let subscription_count = subscriptions.len();
+    subscriptions.retain(|subscription| subscription.guild_id != guild_id || !subscription.class_id.eq(class_id));
+    let language = msg_language(msg).await;
+
+    if subscriptions.len() == subscription_count {
+        return reply(ctx, msg, &tr!(language, "subscribe.not_found.title"), tr!(language, "subscribe.not_found.body", guild_prefix(msg.guild_id).await)).await;
+    }
+
+    overwrite_records(CLASS_SUBSCRIPTION_FILE, &subscriptions).await.map_err(|e| e.to_string())?;
+
+    reply(ctx, msg, &tr!(language, "subscribe.removed.title"), tr!(language, "subscribe.removed.body", class_id)).await
+}
+
+async fn add_subscription(ctx: &Context, msg: &Message, guild_id: u64, class_id: String) -> CommandResult {
+    let subscriptions = read_records::<ClassSubscription>(CLASS_SUBSCRIPTION_FILE).await;
+    let is_subscribed = subscriptions
+        .iter()
+        .any(|subscription| subscription.channel_id == *msg.channel_id.as_u64() && subscription.class_id.eq(&class_id));
+    let language = msg_language(msg).await;
+
+    if is_subscribed {
+        return reply(ctx, msg, &tr!(language, "subscribe.already.title"), tr!(language, "subscribe.already.body")).await;
+    }
+
+    let timeout = interaction_timeout(msg.guild_id).await;
+    let consent_msg = msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .colour(PRIMARY_COLOR)
+            .title(tr!(language, "subscribe.consent.title"))
+            .description(tr!(language, "subscribe.consent.body", class_id))
+            .footer(|f| f.text(tr!(language, "subscribe.consent.footer", timeout.as_secs() / 60)))
+        );
+        m.components(|c| c.create_action_row(|ar| ar
+            .create_button(|b| b
+                .custom_id(CONSENT)
+                .label(tr!(language, "subscribe.consent.button"))
+                .style(ButtonStyle::Primary)
+            )
+        ))
+    }).await?;
+
+    let mut cib = consent_msg
+        .await_component_interactions(&ctx)
+        .timeout(timeout)
+        .await;
+
+    while let Some(mci) = cib.next().await {
+        let member_id = *mci.user.id.as_u64();
+        let member_language = helper::language(&member_id, msg.guild_id).await;
+        let class_details = match active_new_binusmaya_api(&member_id).await {
+            Some(binusmaya_api) => binusmaya_api.get_class_details(class_id.clone()).await.ok(),
+            None => None,
+        };
+
+        let class_details = match class_details {
+            Some(class_details) => class_details,
+            None => {
+                mci.create_interaction_response(&ctx, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource);
+                    r.interaction_response_data(|d| d
+                        .flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                        .content(tr!(member_language, "subscribe.consent.unreadable"))
+                    )
+                }).await?;
+
+                continue;
+            }
+        };
+
+        let subscription = ClassSubscription {
+            guild_id,
+            channel_id: *msg.channel_id.as_u64(),
+            class_id: class_id.clone(),
+            course_name: class_details.course_title_en.clone(),
+            class_code: class_details.class_code.clone(),
+            course_code: class_details.course_code.clone(),
+            source_member_id: member_id,
+            source_account: active_account(&member_id).await,
+        };
+
+        let mut subscriptions = read_records::<ClassSubscription>(CLASS_SUBSCRIPTION_FILE).await;
+        subscriptions.push(subscription);
+        overwrite_records(CLASS_SUBSCRIPTION_FILE, &subscriptions).await.map_err(|e| e.to_string())?;
+
+        mci.create_interaction_response(&ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage);
+            r.interaction_response_data(|d| {
+                d.create_embed(|e| e
+                    .colour(PRIMARY_COLOR)
+                    .title(tr!(language, "subscribe.mirrored.title"))
+                    .description(tr!(language, "subscribe.mirrored.body", class_details.class_code, class_details.course_title_en, NOTICE_BEFORE_START, mci.user.mention()))
+                );
+                d.components(|c| c)
+            })
+        }).await?;
+
+        return Ok(());
+    }
+
+    consent_msg.delete(&ctx.http).await?;
+    reply(ctx, msg, &tr!(language, "subscribe.timed_out.title"), tr!(language, "subscribe.timed_out.body")).await
+}
+
+#[command]
+#[only_in("guild")]
+#[description("Mirror the upcoming sessions and the announcements of a class into this channel, a registered member of the class has to agree to be the data source. Use `=classes` to get the class id")]
+#[usage("[class [class id] | remove [class id] | list]")]
+#[example("class 0a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d")]
+#[example("list")]
+async fn subscribe(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => *guild_id.as_u64(),
+        None => return Ok(()),
+    };
+
+    let mut args = Args::new(args.message(), &[Delimiter::Single(' '), Delimiter::Single(';')]);
+    let action = args.single::<String>().unwrap_or_default();
+
+    if action.eq("list") {
+        return list_subscriptions(ctx, msg, guild_id).await;
+    }
+
+    let language = msg_language(msg).await;
+    if !is_guild_admin(ctx, msg).await {
+        return reply(ctx, msg, &tr!(language, "command.missing_permission.title"), tr!(language, "subscribe.missing_permission")).await;
+    }
+
+    match (action.as_str(), args.single::<String>()) {
+        ("class", Ok(class_id)) => add_subscription(ctx, msg, guild_id, class_id).await,
+        ("remove", Ok(class_id)) => remove_subscription(ctx, msg, guild_id, &class_id).await,
+        _ => reply(ctx, msg, &tr!(language, "command.invalid_argument.title"), tr!(language, "command.invalid_argument.body", guild_prefix(msg.guild_id).await, "subscribe")).await,
+    }
+}
@@ -1,90 +1,26 @@
-use std::ops::Add;
-
-use chrono::Duration;
 use serenity::{
-    framework::standard::{macros::command, CommandResult},
+    framework::standard::{macros::command, Args, CommandResult},
     model::prelude::*,
     prelude::*,
 };
 
-use crate::{
-    api::new_binusmaya_api::NewBinusmayaAPI,
-    consts::{PRIMARY_COLOR, NEWBINUSMAYA_USER_DATA},
-};
+use crate::discord::{helper::{msg_language, new_binusmaya_api_or_reply, parse_account_arg}, render};
 
 #[command]
 #[description("Get upcoming sessions")]
-async fn upcoming(ctx: &Context, msg: &Message) -> CommandResult {
-    let user_data = NEWBINUSMAYA_USER_DATA.clone();
-
-    if user_data.lock().await.contains_key(msg.author.id.as_u64()) {
-        let jwt_exp = user_data
-            .lock()
-            .await
-            .get(msg.author.id.as_u64())
-            .unwrap()
-            .last_registered
-            .add(Duration::weeks(52));
-        let now = chrono::offset::Local::now();
+#[usage("[--account [account name]]")]
+async fn upcoming(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, _) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+    let upcoming_session = binusmaya_api.get_upcoming_sessions().await.unwrap_or(None);
+    let language = msg_language(msg).await;
 
-        if jwt_exp > now {
-            let binusmaya_api = NewBinusmayaAPI {
-                token: user_data
-                    .lock()
-                    .await
-                    .get(msg.author.id.as_u64())
-                    .unwrap()
-                    .auth
-                    .clone(),
-            };
-            let upcoming_session = binusmaya_api.get_upcoming_sessions().await.unwrap_or(None);
+    msg.channel_id
+        .send_message(&ctx.http, |m| m.embed(|e| render::upcoming_embed(e, upcoming_session.as_ref(), language)))
+        .await?;
 
-            if let Some(session) = upcoming_session {
-                msg.channel_id
-                    .send_message(&ctx.http, |m| {
-                        m.embed(|e| {
-                            e.title("Upcoming Session")
-                                .description(session)
-                                .colour(PRIMARY_COLOR)
-                        })
-                    })
-                    .await?;
-            } else {
-                msg.channel_id
-                    .send_message(&ctx.http, |m| {
-                        m.embed(|e| {
-                            e.title("Upcoming Session")
-                                .description(format!("{}", "No upcoming session"))
-                                .colour(PRIMARY_COLOR)
-                        })
-                    })
-                    .await?;
-            }
-        } else {
-            msg.channel_id
-                .send_message(&ctx.http, |m| {
-                    m.embed(|e| {
-                        e.colour(PRIMARY_COLOR).field(
-                            "Your bearer token has expired",
-                            "please re-register using `=add` command",
-                            false,
-                        )
-                    })
-                })
-                .await?;
-        }
-    } else {
-        msg.channel_id
-            .send_message(&ctx.http, |m| {
-                m.embed(|e| {
-                    e.colour(PRIMARY_COLOR).field(
-                        "You're not registered",
-                        "please register first using `=register` command",
-                        false,
-                    )
-                })
-            })
-            .await?;
-    }
     Ok(())
 }
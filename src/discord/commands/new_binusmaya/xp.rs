@@ -0,0 +1,36 @@
+use serenity::framework::standard::macros::command;
+use serenity::framework::standard::{Args, CommandResult};
+use serenity::model::prelude::*;
+use serenity::prelude::*;
+
+use crate::{
+    consts::PRIMARY_COLOR,
+    discord::helper::{guild_prefix, msg_language, new_binusmaya_api_or_reply, parse_account_arg},
+};
+
+#[command]
+#[usage("[--account [account name]]")]
+#[description("Get the XP points of your new binusmaya account")]
+pub async fn xp(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (account, _) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+    let binusmaya_api = match new_binusmaya_api_or_reply(ctx, msg, &account).await? {
+        Some(binusmaya_api) => binusmaya_api,
+        None => return Ok(()),
+    };
+    let user_profile = binusmaya_api.get_user_profile().await?;
+    let language = msg_language(msg).await;
+    let prefix = guild_prefix(msg.guild_id).await;
+
+    msg.channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(tr!(language, "xp.title"))
+                    .description(tr!(language, "xp.body", msg.author.mention(), user_profile.xP_point))
+                    .footer(|f| f.text(tr!(language, "xp.footer", prefix)))
+                    .colour(PRIMARY_COLOR)
+            })
+        })
+        .await?;
+
+    Ok(())
+}
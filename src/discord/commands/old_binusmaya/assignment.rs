@@ -1,9 +1,8 @@
-use std::{fmt::Display, path::PathBuf, str::FromStr};
+use std::{borrow::Cow, fmt::Display, str::FromStr};
 use futures::StreamExt;
-use serenity::{builder::{CreateActionRow, CreateButton}, model::{interactions::message_component::ButtonStyle, prelude::*}, framework::standard::{CommandResult, macros::command}, prelude::*};
+use serenity::{builder::{CreateActionRow, CreateButton, CreateEmbed}, http::AttachmentType, model::{interactions::message_component::ButtonStyle, prelude::*}, framework::standard::{Args, CommandResult, macros::command}, prelude::*};
 
-use crate::{discord::{helper::*, commands::old_binusmaya::helper::*}, consts::{OLDBINUSMAYA_USER_DATA, PRIMARY_COLOR}, api::old_binusmaya_api::OldBinusmayaAPI};
-use tempdir::TempDir;
+use crate::{api::old_binusmaya_api::{AssignmentList, OldBinusmayaAPI}, discord::{archive::{archive_name, bundle_files, is_uploadable, zip_files, ArchiveFile}, helper::*, commands::old_binusmaya::helper::*, render}, consts::{ASSIGNMENT_FILE_CACHE, PRIMARY_COLOR}, locale::Language};
 
 enum AssignmentInteraction {
 	Individual,
@@ -38,13 +37,20 @@ impl FromStr for AssignmentInteraction {
 }
 
 impl AssignmentInteraction {
-	fn button(&self) -> Vec<CreateButton> {
+	fn label(&self, language: Language) -> String {
+		match self {
+			Self::Individual | Self::DownloadIndividual => tr!(language, "assignment.individual"),
+			Self::Group | Self::DownloadGroup => tr!(language, "assignment.group"),
+		}
+	}
+
+	fn button(&self, language: Language) -> Vec<CreateButton> {
 		let mut btn_vec: Vec<CreateButton> = Vec::new();
 		btn_vec.reserve(2);
 
 		let mut btn = CreateButton::default();
 		btn.custom_id(self);
-		btn.label(self);
+		btn.label(self.label(language));
 		btn.style(ButtonStyle::Primary);
 
 		let mut download_btn = CreateButton::default();
@@ -53,7 +59,7 @@ impl AssignmentInteraction {
 		} else {
 			download_btn.custom_id(Self::DownloadIndividual);
 		}
-		download_btn.label("Get question files");
+		download_btn.label(tr!(language, "assignment.get_files"));
 		download_btn.style(ButtonStyle::Secondary);
 
 		btn_vec.push(btn);
@@ -62,18 +68,18 @@ impl AssignmentInteraction {
 		btn_vec
 	}
 
-	pub fn group_action_row() -> CreateActionRow {
+	pub fn group_action_row(language: Language) -> CreateActionRow {
 		let mut ar = CreateActionRow::default();
-		for btn in Self::Group.button() {
+		for btn in Self::Group.button(language) {
 			ar.add_button(btn);
 		}
 
 		ar
 	}
 	
-	pub fn individual_action_row() -> CreateActionRow {
+	pub fn individual_action_row(language: Language) -> CreateActionRow {
 		let mut ar = CreateActionRow::default();
-		for btn in Self::Individual.button() {
+		for btn in Self::Individual.button(language) {
 			ar.add_button(btn);
 		}
 
@@ -81,191 +87,199 @@ impl AssignmentInteraction {
 	}
 }
 
-#[command]
-#[description("Get list of assignments")]
-#[aliases("as")]
-async fn assignment(ctx: &Context, msg: &Message) -> CommandResult {
-	let user_data = OLDBINUSMAYA_USER_DATA.clone();
-	let mut user_data_content = user_data.lock().await;
-	
-	if user_data_content.contains_key(msg.author.id.as_u64()) {
-		let mut binusmaya_api = OldBinusmayaAPI { cookie: user_data_content.get(msg.author.id.as_u64()).unwrap().to_string() };
-		let session_status = binusmaya_api.check_session().await?.session_status;
+/// Sends the question files of the assignments as one zip, the files are cached so they're downloaded once
+async fn send_question_files(ctx: &Context, m: &Message, binusmaya_api: &OldBinusmayaAPI, course_code: &str, title: &str, assignment_list: &AssignmentList, language: Language) -> CommandResult {
+	if assignment_list.assignments.is_empty() {
+		return Ok(());
+	}
 
-		if session_status == 0 {
-			binusmaya_api = update_cookie(msg.author.id.as_u64(), binusmaya_api).await;
-			user_data_content.insert(*msg.author.id.as_u64(), binusmaya_api.cookie.clone());
-		}
+	let mut files: Vec<ArchiveFile> = Vec::new();
+	let mut links: Vec<(String, String)> = Vec::new();
 
-		let course_menu_list = binusmaya_api.get_course_menu_list().await.unwrap();
+	for assignment in assignment_list.assignments.iter() {
+		let cached_file = ASSIGNMENT_FILE_CACHE.lock().await.get(assignment.student_assignment_id);
+		let (file_name, content) = match cached_file {
+			Some(file) => file,
+			None => {
+				let file_name_start_index = assignment.assignment_path_location.rfind('\\').map_or(0, |index| index + 1);
+				let file_name = assignment.assignment_path_location[file_name_start_index..].to_string();
+				let content = binusmaya_api.get_assignment_file(&assignment.assignment_path_location).await?;
 
-		let academic_period_select_menu = select_menu(academic_period_menu_options(&course_menu_list).await).await;
+				ASSIGNMENT_FILE_CACHE.lock().await.insert(assignment.student_assignment_id, (file_name.clone(), content.clone()));
+				(file_name, content)
+			}
+		};
 
-		let m = msg.channel_id.send_message(&ctx.http, |m| {
-			m.content("Choose academic period");
-			m.components(|c| c.add_action_row({
-				let mut ar = CreateActionRow::default();
-				ar.add_select_menu(academic_period_select_menu);
+		if is_uploadable(&content) {
+			files.push((file_name, content));
+		} else {
+			let url = assignment.assignment_url.clone().unwrap_or_else(|| OldBinusmayaAPI::assignment_download_url(&assignment.assignment_path_location));
+			links.push((assignment.title.clone(), url));
+		}
+	}
 
-				ar
-			}))
-		}).await?;
+	let archive_title = match assignment_list.assignments.as_slice() {
+		[assignment] => format!("{} {}", course_code, assignment.title),
+		_ => format!("{} {}", course_code, title),
+	};
+	let bundles = bundle_files(files);
 
-		let mci = m.await_component_interaction(&ctx).await.unwrap();
-		let academic_period_index: usize = mci.data.values.get(0).unwrap().parse().unwrap();
+	for (i, bundle) in bundles.iter().enumerate() {
+		let attachment = AttachmentType::Bytes {
+			data: Cow::from(zip_files(bundle)?),
+			filename: archive_name(&archive_title, i + 1, bundles.len()),
+		};
 
-		let course_select_menu = select_menu(course_menu_options(&course_menu_list, academic_period_index).await).await;
+		m.channel_id.send_files(&ctx.http, vec![attachment], |f| f.content(" ")).await?;
+	}
 
-		mci.create_interaction_response(&ctx, |r| {
-			r.kind(InteractionResponseType::UpdateMessage);
-			r.interaction_response_data(|d| {
-				d.content("Choose course");
-				d.components(|c| c.add_action_row ({
-					let mut ar = CreateActionRow::default();
-					ar.add_select_menu(course_select_menu);
+	if !links.is_empty() {
+		m.channel_id.send_message(&ctx.http, |f| {
+			f.content(tr!(language, "assignment.too_large"));
+			f.components(|c| {
+				for row in links.chunks(5).take(5) {
+					c.create_action_row(|ar| {
+						for (title, url) in row {
+							ar.create_button(|b| b.label(button_label(title)).style(ButtonStyle::Link).url(url));
+						}
 
-					ar
-				}))
+						ar
+					});
+				}
+
+				c
 			})
 		}).await?;
+	}
 
-		let mci = m.await_component_interaction(&ctx).await.unwrap();
-		let course_index: usize = mci.data.values.get(0).unwrap().parse().unwrap();
+	Ok(())
+}
 
-		let chosen_course = &course_menu_list[0][3][academic_period_index][course_index];
+fn assignment_embed<'a>(e: &'a mut CreateEmbed, title: &str, url: &str, pages: &Paginator) -> &'a mut CreateEmbed {
+	e.title(title)
+		.url(url)
+		.description(pages.page())
+		.colour(PRIMARY_COLOR);
 
-		let individual_assignment = binusmaya_api.get_individual_assignments(chosen_course["CRSE_CODE"].as_str().unwrap(), chosen_course["CRSE_ID"].as_str().unwrap(), chosen_course["STRM"].as_str().unwrap(), chosen_course["SSR_COMPONENT"].as_str().unwrap(), chosen_course["CLASS_NBR"].as_str().unwrap()).await?;
-		let group_assignment = binusmaya_api.get_group_assignments(chosen_course["CRSE_CODE"].as_str().unwrap(), chosen_course["CRSE_ID"].as_str().unwrap(), chosen_course["STRM"].as_str().unwrap(), chosen_course["SSR_COMPONENT"].as_str().unwrap(), chosen_course["CLASS_NBR"].as_str().unwrap()).await?;
+	if pages.is_paginated() {
+		e.footer(|f| f.text(pages.footer()));
+	}
 
-		let url = format!("https://binusmaya.binus.ac.id/newStudent/#/class/assignment.{}/{}/{}/{}/{}", chosen_course["CRSE_CODE"].as_str().unwrap(), chosen_course["CRSE_ID"].as_str().unwrap(), chosen_course["STRM"].as_str().unwrap(), chosen_course["SSR_COMPONENT"].as_str().unwrap(), chosen_course["CLASS_NBR"].as_str().unwrap());
+	e
+}
 
-		mci.create_interaction_response(&ctx, |r| {
-			r.kind(InteractionResponseType::UpdateMessage);
-			r.interaction_response_data(|d| {
-				d.content("");
-				d.create_embed(|e| e
-					.title("Individual Assignment(s)")
-					.url(&url)
-					.description(&individual_assignment)
-					.colour(PRIMARY_COLOR)
-				);
-				d.components(|c| c
-					.add_action_row(AssignmentInteraction::group_action_row())
-				)
-			})
-		}).await?;
+#[command]
+#[description("Get list of assignments")]
+#[aliases("as")]
+#[usage("[--account [account name]]")]
+async fn assignment(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let (account, _) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+	let binusmaya_api = match old_binusmaya_api_or_reply(ctx, msg, &account).await? {
+		Some(binusmaya_api) => binusmaya_api,
+		None => return Ok(()),
+	};
+	let language = msg_language(msg).await;
+
+	let course_menu_list = binusmaya_api.get_course_menu_list().await.unwrap();
+
+	let academic_period_select_menu = select_menu(academic_period_menu_options(&course_menu_list).await, language).await;
+
+	let mut m = msg.channel_id.send_message(&ctx.http, |m| {
+		m.content(tr!(language, "menu.academic_period"));
+		m.components(|c| c.add_action_row({
+			let mut ar = CreateActionRow::default();
+			ar.add_select_menu(academic_period_select_menu);
+
+			ar
+		}))
+	}).await?;
+
+	let mci = match await_invoker_interaction(ctx, msg, &mut m).await? {
+		Some(mci) => mci,
+		None => return Ok(()),
+	};
+	let academic_period_index: usize = mci.data.values.first().unwrap().parse().unwrap();
+
+	let course_select_menu = select_menu(course_menu_options(&course_menu_list, academic_period_index).await, language).await;
+
+	mci.create_interaction_response(&ctx, |r| {
+		r.kind(InteractionResponseType::UpdateMessage);
+		r.interaction_response_data(|d| {
+			d.content(tr!(language, "menu.course"));
+			d.components(|c| c.add_action_row ({
+				let mut ar = CreateActionRow::default();
+				ar.add_select_menu(course_select_menu);
 
-		let mut cib = m.await_component_interactions(&ctx).await;
-		while let Some(mci) = cib.next().await {
-			let assignment_type = AssignmentInteraction::from_str(&mci.data.custom_id).unwrap();
-			
-			match assignment_type {
-    			AssignmentInteraction::Individual => {
-					mci.create_interaction_response(&ctx, |r| {
-						r.kind(InteractionResponseType::UpdateMessage);
-						r.interaction_response_data(|d| {
-							d.content("");
-							d.create_embed(|e| e
-								.title("Individual Assignment(s)")
-								.url(&url)
-								.description(&individual_assignment)
-								.colour(PRIMARY_COLOR)
-							);
-							d.components(|c| c.add_action_row(AssignmentInteraction::group_action_row()))
-						})
-					}).await?;
-				},
-   				AssignmentInteraction::Group => {
-					mci.create_interaction_response(&ctx, |r| {
-						r.kind(InteractionResponseType::UpdateMessage);
-						r.interaction_response_data(|d| {
-							d.create_embed(|e| e
-								.title("Group Assignment(s)")
-								.url(&url)
-								.description(&group_assignment)
-								.colour(PRIMARY_COLOR)
-							);
-							d.components(|c| c.add_action_row(AssignmentInteraction::individual_action_row()))
-						})
-					}).await?;
-				},
-				AssignmentInteraction::DownloadIndividual => {
-					if !individual_assignment.assignments.is_empty() {
-						let mut file_vec: Vec<PathBuf> = Vec::new();
-						file_vec.reserve(5);
-	
-						let tmp_dir = TempDir::new("assignment_files")?;
-	
-						for assignment in individual_assignment.clone().assignments {
-							let file_name_start_index = assignment.assignment_path_location.rfind("\\").unwrap();
-							let file_name = &assignment.assignment_path_location[file_name_start_index+1..];
-							let file_path = tmp_dir.path().join(file_name);
-							
-							binusmaya_api.download_assignment(&assignment.assignment_path_location, &file_path).await?;
-							file_vec.push(file_path);
-						}
-						
-						m.channel_id.send_files(&ctx.http, file_vec.iter(), |f| f.content(" ")).await?;
-					}
-					
-					mci.create_interaction_response(&ctx, |r| {
-						r.kind(InteractionResponseType::UpdateMessage);
-						r.interaction_response_data(|d| {
-							d.content("");
-							d.create_embed(|e| e
-								.title("Individual Assignment(s)")
-								.url(&url)
-								.description(&individual_assignment)
-								.colour(PRIMARY_COLOR)
-							);
-							d.components(|c| c.add_action_row(AssignmentInteraction::group_action_row()))
-						})
-					}).await?;
+				ar
+			}))
+		})
+	}).await?;
+
+	let mci = match await_invoker_interaction(ctx, msg, &mut m).await? {
+		Some(mci) => mci,
+		None => return Ok(()),
+	};
+	let course_index: usize = mci.data.values.first().unwrap().parse().unwrap();
+
+	let chosen_course = &course_menu_list[0][3][academic_period_index][course_index];
+
+	let individual_assignment = binusmaya_api.get_individual_assignments(chosen_course["CRSE_CODE"].as_str().unwrap(), chosen_course["CRSE_ID"].as_str().unwrap(), chosen_course["STRM"].as_str().unwrap(), chosen_course["SSR_COMPONENT"].as_str().unwrap(), chosen_course["CLASS_NBR"].as_str().unwrap()).await?;
+	let group_assignment = binusmaya_api.get_group_assignments(chosen_course["CRSE_CODE"].as_str().unwrap(), chosen_course["CRSE_ID"].as_str().unwrap(), chosen_course["STRM"].as_str().unwrap(), chosen_course["SSR_COMPONENT"].as_str().unwrap(), chosen_course["CLASS_NBR"].as_str().unwrap()).await?;
+
+	let course_code = chosen_course["CRSE_CODE"].as_str().unwrap();
+	let url = format!("https://binusmaya.binus.ac.id/newStudent/#/class/assignment.{}/{}/{}/{}/{}", chosen_course["CRSE_CODE"].as_str().unwrap(), chosen_course["CRSE_ID"].as_str().unwrap(), chosen_course["STRM"].as_str().unwrap(), chosen_course["SSR_COMPONENT"].as_str().unwrap(), chosen_course["CLASS_NBR"].as_str().unwrap());
+
+	let mut individual_pages = Paginator::new(render::assignments(&individual_assignment, language), language);
+	let mut group_pages = Paginator::new(render::assignments(&group_assignment, language), language);
+	let mut show_group = false;
+
+	mci.create_interaction_response(&ctx, |r| {
+		r.kind(InteractionResponseType::UpdateMessage);
+		r.interaction_response_data(|d| {
+			d.content("");
+			d.create_embed(|e| assignment_embed(e, &tr!(language, "assignment.individual_title"), &url, &individual_pages));
+			d.components(|c| individual_pages.add_action_row(c.add_action_row(AssignmentInteraction::group_action_row(language))))
+		})
+	}).await?;
+
+	let mut cib = m.await_component_interactions(&ctx).timeout(interaction_timeout(msg.guild_id).await).await;
+	while let Some(mci) = cib.next().await {
+		if !is_invoker(ctx, msg, &mci).await? {
+			continue;
+		}
+
+		let pages = if show_group { &mut group_pages } else { &mut individual_pages };
 
+		if !pages.turn(&mci.data.custom_id) {
+			match AssignmentInteraction::from_str(&mci.data.custom_id).unwrap() {
+				AssignmentInteraction::Individual => show_group = false,
+				AssignmentInteraction::Group => show_group = true,
+				AssignmentInteraction::DownloadIndividual => {
+					send_question_files(ctx, &m, &binusmaya_api, course_code, "Individual Assignments", &individual_assignment, language).await?;
 				},
 				AssignmentInteraction::DownloadGroup => {
-					if !group_assignment.assignments.is_empty() {
-						let mut file_vec: Vec<PathBuf> = Vec::new();
-						file_vec.reserve(5);
-	
-						let tmp_dir = TempDir::new("assignment_files")?;
-	
-						for assignment in group_assignment.clone().assignments {
-							let file_name_start_index = assignment.assignment_path_location.rfind("\\").unwrap();
-							let file_name = &assignment.assignment_path_location[file_name_start_index+1..];
-							let file_path = tmp_dir.path().join(file_name);
-							
-							binusmaya_api.download_assignment(&assignment.assignment_path_location, &file_path).await?;
-							file_vec.push(file_path);
-						}
-
-						m.channel_id.send_files(&ctx.http, file_vec.iter(), |f| f.content(" ")).await?;
-					}
-
-					mci.create_interaction_response(&ctx, |r| {
-						r.kind(InteractionResponseType::UpdateMessage);
-						r.interaction_response_data(|d| {
-							d.create_embed(|e| e
-								.title("Group Assignment(s)")
-								.url(&url)
-								.description(&group_assignment)
-								.colour(PRIMARY_COLOR)
-							);
-							d.components(|c| c.add_action_row(AssignmentInteraction::individual_action_row()))
-						})
-					}).await?;
-
+					send_question_files(ctx, &m, &binusmaya_api, course_code, "Group Assignments", &group_assignment, language).await?;
 				},
 			}
 		}
-	} else {
-		msg.channel_id.send_message(&ctx.http, |m| {
-			m.embed(|e| e
-				.colour(PRIMARY_COLOR)
-				.field("You're Not Registered", "You haven't registered yet, use `=register` command to register your account", false)
-			)
+
+		let (title, pages, action_row) = if show_group {
+			(tr!(language, "assignment.group_title"), &group_pages, AssignmentInteraction::individual_action_row(language))
+		} else {
+			(tr!(language, "assignment.individual_title"), &individual_pages, AssignmentInteraction::group_action_row(language))
+		};
+
+		mci.create_interaction_response(&ctx, |r| {
+			r.kind(InteractionResponseType::UpdateMessage);
+			r.interaction_response_data(|d| {
+				d.content("");
+				d.create_embed(|e| assignment_embed(e, &title, &url, pages));
+				d.components(|c| pages.add_action_row(c.add_action_row(action_row)))
+			})
 		}).await?;
 	}
 
+	disable_components(ctx, &mut m).await?;
+
 	Ok(())
-}
\ No newline at end of file
+}
@@ -1,40 +1,25 @@
-use serenity::framework::standard::CommandResult;
+use serenity::framework::standard::{Args, CommandResult};
 use serenity::framework::standard::macros::command;
 use serenity::model::prelude::*;
 use serenity::prelude::*;
 
-use crate::{consts::{OLDBINUSMAYA_USER_DATA, PRIMARY_COLOR}, api::old_binusmaya_api::OldBinusmayaAPI, discord::helper::update_cookie};
+use crate::discord::{helper::{msg_language, old_binusmaya_api_or_reply, parse_account_arg}, render};
 
 #[command]
-async fn sat(ctx: &Context, msg: &Message) -> CommandResult {
-	let user_data = OLDBINUSMAYA_USER_DATA.clone();
-	let mut user_data_content = user_data.lock().await;
-	
-	if user_data_content.contains_key(msg.author.id.as_u64()) {
-		let mut binusmaya_api = OldBinusmayaAPI { cookie: user_data_content.get(msg.author.id.as_u64()).unwrap().to_string() };
-		let session_status = binusmaya_api.check_session().await?.session_status;
+#[usage("[--account [account name]]")]
+async fn sat(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+	let (account, _) = parse_account_arg(msg.author.id.as_u64(), &args).await;
+	let binusmaya_api = match old_binusmaya_api_or_reply(ctx, msg, &account).await? {
+		Some(binusmaya_api) => binusmaya_api,
+		None => return Ok(()),
+	};
 
-		if session_status == 0 {
-			binusmaya_api = update_cookie(msg.author.id.as_u64(), binusmaya_api).await;
-			user_data_content.insert(*msg.author.id.as_u64(), binusmaya_api.cookie.clone());
-		}
+	let sat = binusmaya_api.get_sat().await?;
+	let language = msg_language(msg).await;
 
-		let sat = binusmaya_api.get_sat().await?;
-
-		msg.channel_id.send_message(&ctx.http, |m| {
-			m.embed(|e| e
-				.colour(PRIMARY_COLOR)
-				.field("SAT Points", sat, true)
-			)
-		}).await?;
-	} else {
-		msg.channel_id.send_message(&ctx.http, |m| {
-			m.embed(|e| e
-				.colour(PRIMARY_COLOR)
-				.field("You're Not Registered", "You haven't registered yet, use `=register` command to register your account", false)
-			)
-		}).await?;
-	}
+	msg.channel_id.send_message(&ctx.http, |m| {
+		m.embed(|e| render::sat_embed(e, &sat, language))
+	}).await?;
 
 	Ok(())
 }	
\ No newline at end of file
@@ -1,5 +1,4 @@
 use chrono::{DateTime, Duration, Local, NaiveDate};
-use csv_async::AsyncReaderBuilder;
 use futures::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use serenity::{framework::standard::{
@@ -12,24 +11,28 @@ use serenity::{
 };
 use std::{
     collections::HashSet,
-    fs::{metadata, read_to_string, File},
+    error::Error,
+    fs::{metadata, File},
     sync::Arc,
     thread::{self, sleep}, process::Command,
 };
 use tokio::fs::{write, File as TokioFile};
 
-use crate::{discord::{commands::{
+use crate::{discord::{render, class_subscription::class_subscription_event, class_sync::class_sync_event, scheduler::{finish_task, start_task, DAILY_TASK, TOKEN_REFRESH_TASK}, xp_leaderboard::xp_movers_event, commands::{
+    admin::{
+        broadcast::*, daily::*, health::*, refresh::*, reload::*, tasks::*, users::*,
+    },
     general::{
-        about::*, add::*, ping::*, register::*
+        about::*, account::*, add::*, config::*, language::*, ping::*, profile::*, register::*
     },
     new_binusmaya::{
-        announcement::*, classes::*, session::*, ongoing::*, 
-        schedule::*, upcoming::*,
+        announcement::*, classes::*, classmates::*, leaderboard::*, lecturers::*, materials::*, session::*, ongoing::*, progress::*, 
+        schedule::*, subscribe::*, upcoming::*, xp::*,
     },
     old_binusmaya::{
         sat::*, comserv::*, assignment::*,
     }
-}, helper::update_cookie_all}, consts::{OLDBINUSMAYA_USER_FILE, LOGIN_FILE, NEWBINUSMAYA_USER_DATA, NEWBINUSMAYA_USER_FILE}, api::{new_binusmaya_api::*, old_binusmaya_api::{BinusianData}, self}};
+}, helper::{decrypt_password, decrypt_token, guild_prefix, overwrite_records, read_records, replace_new_binusmaya_record, language, load_active_accounts, load_guild_configs, load_user_languages, msg_language, load_old_binusmaya_user_data, warm_up_cookies}}, consts::{ACTIVE_ACCOUNT, ACTIVE_ACCOUNT_FILE, ARG_DELIMITER, DEFAULT_ACCOUNT, DEFAULT_PREFIX, GUILD_CONFIG, GUILD_CONFIG_FILE, OLDBINUSMAYA_USER_DATA, OLDBINUSMAYA_USER_FILE, LOGIN_FILE, STORED_FILES, NEWBINUSMAYA_USER_DATA, NEWBINUSMAYA_USER_FILE, PASSWORD_CRYPT, PRIMARY_COLOR, TOKEN_CRYPT, USER_LANGUAGE, USER_LANGUAGE_FILE}, api::{new_binusmaya_api::*, old_binusmaya_api::{BinusianData}, self}, crypto::{CryptoError, Keyring}};
 
 use std::env;
 
@@ -37,12 +40,93 @@ const VIRTUAL_CLASS: &str = "Virtual Class";
 const FORUM: &str = "Forum";
 const GSLC: &str = "GSLC";
 const ASSIGNMENT: &str = "Assignment";
+/// Stored files that are loaded into memory when the bot starts
+const USER_DATA_FILES: [&str; 5] = [NEWBINUSMAYA_USER_FILE, OLDBINUSMAYA_USER_FILE, ACTIVE_ACCOUNT_FILE, GUILD_CONFIG_FILE, USER_LANGUAGE_FILE];
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct NewBinusmayaUserRecord {
     pub member_id: u64,
     pub auth: String,
     pub last_registered: DateTime<Local>,
+    /// Only stored for users who opted in to automatic token refresh, the password is encrypted
+    pub user_credential: Option<UserCredential>,
+    /// Records registered before named accounts existed belong to the default account
+    pub account: Option<String>,
+}
+
+impl NewBinusmayaUserRecord {
+    pub fn account_name(&self) -> &str {
+        self.account.as_deref().unwrap_or(DEFAULT_ACCOUNT)
+    }
+}
+
+/// A Discord user and the name of one of their BINUSMAYA accounts
+pub type AccountKey = (u64, String);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ActiveAccountRecord {
+    pub member_id: u64,
+    pub account: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UserLanguageRecord {
+    pub member_id: u64,
+    pub language: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct GuildConfig {
+    pub guild_id: u64,
+    pub prefix: Option<String>,
+    pub language: Option<String>,
+    pub reminder_channel: Option<u64>,
+    pub admin_role: Option<u64>,
+    /// Space separated names of the command groups that are disabled in the guild
+    pub disabled_groups: Option<String>,
+    /// Gives registered members a role for each of their class codes
+    pub class_roles: Option<bool>,
+    /// Creates a text channel for each course of the registered members, only used if `class_roles` is on
+    pub course_channels: Option<bool>,
+    /// Minutes until the buttons and menus of a command stop working
+    pub interaction_timeout: Option<u64>,
+}
+
+impl GuildConfig {
+    pub fn new(guild_id: u64) -> Self {
+        GuildConfig {
+            guild_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_group_enabled(&self, group_name: &str) -> bool {
+        match &self.disabled_groups {
+            Some(disabled_groups) => !disabled_groups.split(' ').any(|name| name.eq_ignore_ascii_case(group_name)),
+            None => true,
+        }
+    }
+
+    pub fn set_group_enabled(&mut self, group_name: &str, enabled: bool) {
+        let mut disabled_groups: Vec<String> = self
+            .disabled_groups
+            .as_deref()
+            .unwrap_or_default()
+            .split(' ')
+            .filter(|name| !name.is_empty() && !name.eq_ignore_ascii_case(group_name))
+            .map(|name| name.to_string())
+            .collect();
+
+        if !enabled {
+            disabled_groups.push(group_name.to_string());
+        }
+
+        self.disabled_groups = if disabled_groups.is_empty() {
+            None
+        } else {
+            Some(disabled_groups.join(" "))
+        };
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -78,12 +162,28 @@ impl UserBinusianData {
 pub struct OldBinusmayaUserRecord {
     pub member_id: u64,
     pub user_credential: UserCredential,
-    pub binusian_data: UserBinusianData
+    pub binusian_data: UserBinusianData,
+    pub account: Option<String>,
+}
+
+impl OldBinusmayaUserRecord {
+    pub fn account_name(&self) -> &str {
+        self.account.as_deref().unwrap_or(DEFAULT_ACCOUNT)
+    }
 }
 
 pub struct NewBinusmayaUserAuthInfo {
     pub auth: String,
     pub last_registered: DateTime<Local>,
+    pub user_credential: Option<UserCredential>,
+    pub refresh_failed: bool,
+}
+
+pub struct OldBinusmayaUserAuthInfo {
+    pub cookie: Option<String>,
+    pub user_credential: UserCredential,
+    pub binusian_data: UserBinusianData,
+    pub login_failed: bool,
 }
 
 pub struct ShardManagerContainer;
@@ -93,12 +193,12 @@ impl TypeMapKey for ShardManagerContainer {
 }
 
 #[group]
-#[commands(ping, register, add, about)]
+#[commands(ping, register, add, account, profile, config, language, about)]
 pub struct General;
 
 #[group]
 #[summary("Commands that fetch data from new binusmaya")]
-#[commands(schedule, session, classes, ongoing, upcoming, announcement)]
+#[commands(schedule, session, classes, ongoing, upcoming, announcement, subscribe, classmates, xp, leaderboard, lecturers, progress, materials)]
 pub struct NewBinusmaya;
 
 #[group]
@@ -106,6 +206,13 @@ pub struct NewBinusmaya;
 #[commands(sat, comserv, assignment)]
 pub struct OldBinusmaya;
 
+#[group]
+#[owners_only]
+#[prefixes("admin")]
+#[summary("Commands for the bot owners to operate the bot")]
+#[commands(users, refresh, reload, daily, tasks, health, broadcast)]
+pub struct Admin;
+
 pub struct Handler;
 
 fn start_third_party_apps() {
@@ -126,33 +233,47 @@ fn start_third_party_apps() {
         .expect("Failed to start browsermob-proxy");
 }
 
+/// Replaces the local copy of a stored file with the one in dropbox, it's left empty if it isn't stored yet
+pub async fn download_stored_file(file_name: &str) -> Result<(), Box<dyn Error>> {
+    let content = api::dropbox_api::download_file(file_name.to_string()).await?;
+    write(file_name, content.unwrap_or_default().as_bytes()).await?;
+
+    Ok(())
+}
+
 async fn fetch_file() {
     TokioFile::create(LOGIN_FILE)
         .await
         .expect("Error in creating login.txt");
 
-    TokioFile::create(NEWBINUSMAYA_USER_FILE).await.expect("Error in creating new binusmaya file");
-
-    TokioFile::create(OLDBINUSMAYA_USER_FILE).await.expect("Error in creating old binusmaya file");
-    
+    for file_name in STORED_FILES {
+        download_stored_file(file_name)
+            .await
+            .unwrap_or_else(|e| panic!("Error in creating {}: {:?}", file_name, e));
+    }
 
-    let new_binusmaya_user_content = api::dropbox_api::download_file(NEWBINUSMAYA_USER_FILE.to_string())
-        .await
-        .unwrap();
+    println!("File created successfully");
+}
 
-    if let Some(content) = new_binusmaya_user_content {
-        write(NEWBINUSMAYA_USER_FILE, content.as_bytes()).await.unwrap();
+/// Downloads the user data files again and replaces the user data in memory with them
+pub async fn reload_user_data() -> Result<(), Box<dyn Error>> {
+    for file_name in USER_DATA_FILES {
+        download_stored_file(file_name).await?;
     }
 
-    let old_binusmaya_user_content = api::dropbox_api::download_file(OLDBINUSMAYA_USER_FILE.to_string())
-        .await
-        .unwrap();
+    NEWBINUSMAYA_USER_DATA.lock().await.clear();
+    OLDBINUSMAYA_USER_DATA.lock().await.clear();
+    ACTIVE_ACCOUNT.lock().await.clear();
+    GUILD_CONFIG.lock().await.clear();
+    USER_LANGUAGE.lock().await.clear();
 
-    if let Some(content) = old_binusmaya_user_content {
-        write(OLDBINUSMAYA_USER_FILE, content.as_bytes()).await.unwrap();
-    }
-    
-    println!("File created successfully");
+    load_new_binusmaya_user_data().await;
+    load_old_binusmaya_user_data().await;
+    load_active_accounts().await;
+    load_guild_configs().await;
+    load_user_languages().await;
+
+    Ok(())
 }
 
 async fn update_student_progress(new_binusmaya_api: &NewBinusmayaAPI, schedule_details: ScheduleDetails) {
@@ -184,19 +305,20 @@ async fn post_forum_reminder(ctx: &Context, new_binusmaya_api: &NewBinusmayaAPI,
         let private_channel = UserId(*user_id).create_dm_channel(&ctx.http).await;
 
         if let Ok(channel) = private_channel {
+            let language = language(user_id, None).await;
             let mut content = MessageBuilder::new();
-            content.push_bold_line("Don't forget to post a forum, today's the deadline.");
+            content.push_bold_line(tr!(language, "reminder.forum.body"));
             
             class_session.resources.list.iter().for_each(|r| {
                 if r.resource_type.eq(FORUM) && r.progress_status != 2 {
-                    content.push_quote_line(format!("**{} - Session {}**", schedule_details.content, schedule_details.custom_param.session_number))
-                        .push_quote_line(format!("[forum link](https://newbinusmaya.binus.ac.id/lms/course/{}/forum/{})", schedule_details.custom_param.class_id, schedule_details.custom_param.class_session_id));
+                    content.push_quote_line(format!("**{} - {} {}**", schedule_details.content, tr!(language, "session.session"), schedule_details.custom_param.session_number))
+                        .push_quote_line(format!("[{}]({})", tr!(language, "reminder.forum.link"), render::forum_url(&schedule_details.custom_param.class_id, &schedule_details.custom_param.class_session_id)));
                 } 
             });
     
             channel.id.send_message(&ctx.http, |m| {
                 m.embed(|e| e
-                    .title("Post GSLC Forum Reminder")
+                    .title(tr!(language, "reminder.forum.title"))
                     .description(content.build())
     
                 )
@@ -210,7 +332,7 @@ async fn post_forum_reminder(ctx: &Context, new_binusmaya_api: &NewBinusmayaAPI,
 async fn loop_student_schedule(ctx: &Context) {
     let user_data = NEWBINUSMAYA_USER_DATA.clone();
     stream::iter(user_data.lock().await.iter())
-        .for_each_concurrent(8, |(member_id, user_auth_info)| async move {
+        .for_each_concurrent(8, |((member_id, _), user_auth_info)| async move {
             let binusmaya_api = NewBinusmayaAPI {
                 token: user_auth_info.auth.to_string(),
             };
@@ -242,6 +364,18 @@ async fn loop_student_schedule(ctx: &Context) {
         .await;
 }
 
+/// Updates today's student progress and posts the forum reminders unless it's already running, returns whether it ran
+pub async fn run_daily_task(ctx: &Context) -> bool {
+    if !start_task(DAILY_TASK).await {
+        return false;
+    }
+
+    loop_student_schedule(ctx).await;
+    finish_task(DAILY_TASK).await;
+
+    true
+}
+
 async fn daily_event(ctx: &Context) {
     loop {
         let metadata = metadata(LOGIN_FILE).unwrap();
@@ -249,7 +383,7 @@ async fn daily_event(ctx: &Context) {
         if let Ok(time) = metadata.modified() {
             let last_login = DateTime::<Local>::from(time).date();
             if last_login.succ().eq(&chrono::offset::Local::now().date()) {
-                loop_student_schedule(ctx).await;
+                run_daily_task(ctx).await;
 
                 File::create(LOGIN_FILE).unwrap_or_else(|e| {
                     panic!("Error in creating file: {:?}", e);
@@ -263,31 +397,217 @@ async fn daily_event(ctx: &Context) {
     }
 }
 
+async fn refresh_token(ctx: &Context, account_key: AccountKey, user_credential: UserCredential) {
+    let refreshed_token = match decrypt_password(&user_credential.password) {
+        Ok((password, _)) => refresh_new_binusmaya_token(&UserCredential {
+            email: user_credential.email.clone(),
+            password,
+        })
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to refresh bearer token of {:?}: {:?}", account_key, e);
+            None
+        }),
+        Err(e) => {
+            eprintln!("Failed to refresh bearer token of {:?}: {}", account_key, e);
+            None
+        }
+    };
+
+    match refreshed_token {
+        Some(auth) => {
+            let user_record = NewBinusmayaUserRecord {
+                member_id: account_key.0,
+                auth: TOKEN_CRYPT.encrypt(&auth),
+                last_registered: Local::now(),
+                user_credential: Some(user_credential.clone()),
+                account: Some(account_key.1.clone()),
+            };
+
+            NEWBINUSMAYA_USER_DATA.lock().await.insert(
+                account_key.clone(),
+                NewBinusmayaUserAuthInfo {
+                    auth,
+                    last_registered: user_record.last_registered,
+                    user_credential: Some(user_credential),
+                    refresh_failed: false,
+                },
+            );
+
+            if let Err(e) = replace_new_binusmaya_record(user_record).await {
+                eprintln!("Failed to store refreshed bearer token of {:?}: {:?}", account_key, e);
+            }
+        }
+        None => {
+            if let Some(user_auth_info) = NEWBINUSMAYA_USER_DATA.lock().await.get_mut(&account_key) {
+                user_auth_info.refresh_failed = true;
+            }
+
+            if let Ok(channel) = UserId(account_key.0).create_dm_channel(&ctx.http).await {
+                let language = language(&account_key.0, None).await;
+                let _ = channel.id.send_message(&ctx.http, |m| {
+                    m.embed(|e| e
+                        .colour(PRIMARY_COLOR)
+                        .field(tr!(language, "account.refresh_failed.title"), tr!(language, "account.refresh_failed.body", account_key.1, DEFAULT_PREFIX, account_key.1), false)
+                    )
+                }).await;
+            }
+        }
+    }
+}
+
+/// Refreshes the bearer token of users who stored their credential a week before it expires
+async fn token_refresh_event(ctx: &Context) {
+    loop {
+        let refresh_deadline = Local::now() - Duration::weeks(52) + Duration::days(7);
+        let expiring_accounts: Vec<(AccountKey, UserCredential)> = NEWBINUSMAYA_USER_DATA
+            .lock()
+            .await
+            .iter()
+            .filter(|(_, user_auth_info)| !user_auth_info.refresh_failed && user_auth_info.last_registered < refresh_deadline)
+            .filter_map(|(account_key, user_auth_info)| {
+                user_auth_info.user_credential.clone().map(|user_credential| (account_key.clone(), user_credential))
+            })
+            .collect();
+
+        // the browsermob proxy only handles one headless login at a time
+        start_task(TOKEN_REFRESH_TASK).await;
+        for (account_key, user_credential) in expiring_accounts {
+            refresh_token(ctx, account_key, user_credential).await;
+        }
+        finish_task(TOKEN_REFRESH_TASK).await;
+
+        tokio::time::sleep(Duration::hours(1).to_std().unwrap()).await;
+    }
+}
+
+async fn load_new_binusmaya_user_data() {
+    let mut user_records = read_records::<NewBinusmayaUserRecord>(NEWBINUSMAYA_USER_FILE).await;
+    let mut has_stale_secret = false;
+
+    for record in user_records.iter_mut() {
+        // legacy magic crypt passwords and passwords encrypted with the previous key are re-encrypted with the current key
+        if let Some(user_credential) = record.user_credential.as_mut() {
+            if let Ok((password, true)) = decrypt_password(&user_credential.password) {
+                has_stale_secret = true;
+                user_credential.password = PASSWORD_CRYPT.encrypt(&password);
+            }
+        }
+
+        match decrypt_token(&record.auth) {
+            Ok((auth, is_stale)) => {
+                // plaintext tokens and tokens encrypted with the previous key are re-encrypted with the current key
+                if is_stale {
+                    has_stale_secret = true;
+                    record.auth = TOKEN_CRYPT.encrypt(&auth);
+                }
+
+                NEWBINUSMAYA_USER_DATA.lock().await.insert(
+                    (record.member_id, record.account_name().to_string()),
+                    NewBinusmayaUserAuthInfo {
+                        auth,
+                        last_registered: record.last_registered,
+                        user_credential: record.user_credential.clone(),
+                        refresh_failed: false,
+                    },
+                );
+            }
+            Err(e) => eprintln!("Skipping new binusmaya user {}: {}", record.member_id, e),
+        }
+    }
+
+    if has_stale_secret {
+        match overwrite_records(NEWBINUSMAYA_USER_FILE, &user_records).await {
+            Ok(_) => println!("Re-encrypted bearer tokens and passwords in {}", NEWBINUSMAYA_USER_FILE),
+            Err(e) => eprintln!("Failed to re-encrypt bearer tokens and passwords: {:?}", e),
+        }
+    }
+}
+
+/// Re-encrypts every stored bearer token and password with the current keys.
+/// Set `OLD_TOKEN_KEY`/`OLD_SECRET_KEY` to the previous keys and run the bot with the `rotate-keys` argument
+pub async fn rotate_keys() {
+    fetch_file().await;
+
+    let (mut re_encrypted, mut failed) = (0, 0);
+    let mut re_encrypt = |result: Result<(String, bool), CryptoError>, crypt: &Keyring, secret: &mut String, description: &str, member_id: u64| match result {
+        Ok((plaintext, _)) => {
+            *secret = crypt.encrypt(&plaintext);
+            re_encrypted += 1;
+        }
+        Err(e) => {
+            eprintln!("Couldn't re-encrypt {} of {}: {}", description, member_id, e);
+            failed += 1;
+        }
+    };
+
+    let mut new_binusmaya_records = read_records::<NewBinusmayaUserRecord>(NEWBINUSMAYA_USER_FILE).await;
+    for record in new_binusmaya_records.iter_mut() {
+        re_encrypt(decrypt_token(&record.auth), &TOKEN_CRYPT, &mut record.auth, "bearer token", record.member_id);
+
+        if let Some(user_credential) = record.user_credential.as_mut() {
+            re_encrypt(decrypt_password(&user_credential.password), &PASSWORD_CRYPT, &mut user_credential.password, "password", record.member_id);
+        }
+    }
+
+    overwrite_records(NEWBINUSMAYA_USER_FILE, &new_binusmaya_records)
+        .await
+        .expect("Failed to write new binusmaya user data");
+
+    let mut old_binusmaya_records = read_records::<OldBinusmayaUserRecord>(OLDBINUSMAYA_USER_FILE).await;
+    for record in old_binusmaya_records.iter_mut() {
+        re_encrypt(decrypt_password(&record.user_credential.password), &PASSWORD_CRYPT, &mut record.user_credential.password, "password", record.member_id);
+    }
+
+    overwrite_records(OLDBINUSMAYA_USER_FILE, &old_binusmaya_records)
+        .await
+        .expect("Failed to write old binusmaya user data");
+
+    println!(
+        "Re-encrypted {} secret(s) of {} new binusmaya and {} old binusmaya record(s), {} couldn't be decrypted and were left as they were",
+        re_encrypted,
+        new_binusmaya_records.len(),
+        old_binusmaya_records.len(),
+        failed
+    );
+}
+
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, data_about_bot: Ready) {
         fetch_file().await;
         start_third_party_apps();
         
-        let newbinusmaya_content = read_to_string(NEWBINUSMAYA_USER_FILE).expect("Something's wrong when reading a file");
+        load_new_binusmaya_user_data().await;
+        load_old_binusmaya_user_data().await;
+        load_active_accounts().await;
+        load_guild_configs().await;
+        load_user_languages().await;
 
-        let rdr = AsyncReaderBuilder::new()
-            .has_headers(false)
-            .create_deserializer(newbinusmaya_content.as_bytes());
+        let http = ctx.http.clone();
+        tokio::spawn(async move {
+            warm_up_cookies(&http).await;
+        });
 
-        let mut records = rdr.into_deserialize::<NewBinusmayaUserRecord>();
-        while let Some(record) = records.next().await {
-            let record = record.unwrap();
-            NEWBINUSMAYA_USER_DATA.lock().await.insert(
-                record.member_id,
-                NewBinusmayaUserAuthInfo {
-                    auth: record.auth,
-                    last_registered: record.last_registered,
-                },
-            );
-        }
+        let refresh_ctx = ctx.clone();
+        tokio::spawn(async move {
+            token_refresh_event(&refresh_ctx).await;
+        });
+
+        let class_sync_ctx = ctx.clone();
+        tokio::spawn(async move {
+            class_sync_event(&class_sync_ctx).await;
+        });
+
+        let class_subscription_ctx = ctx.clone();
+        tokio::spawn(async move {
+            class_subscription_event(&class_subscription_ctx).await;
+        });
 
-        update_cookie_all().await;
+        let xp_movers_ctx = ctx.clone();
+        tokio::spawn(async move {
+            xp_movers_event(&xp_movers_ctx).await;
+        });
 
         tokio::spawn(async move {
             println!("{:?} is running", thread::current().id());
@@ -299,6 +619,7 @@ impl EventHandler for Handler {
 }
 
 #[help]
+#[lacking_ownership("hide")]
 #[strikethrough_commands_tip_in_guild("")]
 #[strikethrough_commands_tip_in_dm("")]
 #[embed_success_colour("#3498DB")]
@@ -315,8 +636,47 @@ async fn help(
     Ok(())
 }
 
+pub const COMMAND_GROUPS: [&CommandGroup; 3] = [&GENERAL_GROUP, &NEWBINUSMAYA_GROUP, &OLDBINUSMAYA_GROUP];
+
+fn command_group(cmd_name: &str) -> Option<&'static CommandGroup> {
+    COMMAND_GROUPS.iter().copied().find(|group| {
+        group
+            .options
+            .commands
+            .iter()
+            .any(|command| command.options.names.contains(&cmd_name))
+    })
+}
+
+/// The prefix set with `=config prefix` replaces the default prefix in the guild
 #[hook]
-async fn before(ctx: &Context, msg: &Message, _cmd_name: &str) -> bool {
+async fn dynamic_prefix(_ctx: &Context, msg: &Message) -> Option<String> {
+    Some(guild_prefix(msg.guild_id).await)
+}
+
+#[hook]
+async fn before(ctx: &Context, msg: &Message, cmd_name: &str) -> bool {
+    // `=config` is never disabled so the groups can be enabled again
+    if let (Some(guild_id), Some(group)) = (msg.guild_id, command_group(cmd_name)) {
+        let is_enabled = cmd_name.eq("config")
+            || GUILD_CONFIG
+                .lock()
+                .await
+                .get(guild_id.as_u64())
+                .is_none_or(|guild_config| guild_config.is_group_enabled(group.name));
+
+        if !is_enabled {
+            let language = msg_language(msg).await;
+            let _ = msg.channel_id.send_message(&ctx, |m| {
+                m.embed(|e| e
+                    .colour(PRIMARY_COLOR)
+                    .field(tr!(language, "command.disabled.title"), tr!(language, "command.disabled.body", group.name), false))
+            }).await;
+
+            return false;
+        }
+    }
+
     msg.react(&ctx, '👍').await.unwrap();
 
     true
@@ -324,9 +684,11 @@ async fn before(ctx: &Context, msg: &Message, _cmd_name: &str) -> bool {
 
 #[hook]
 async fn unknown_command(ctx: &Context, msg: &Message, cmd_name: &str) {
+    let language = msg_language(msg).await;
+    let prefix = guild_prefix(msg.guild_id).await;
     msg.channel_id.send_message(&ctx, |m| {
         m.embed(|e| e
-            .field("Couldn't find command name", format!("Couldn't find command named **{}**, use `=help` command to see the command list", cmd_name), false))
+            .field(tr!(language, "command.not_found.title"), tr!(language, "command.not_found.body", cmd_name, prefix), false))
     }).await.unwrap();
 }
 
@@ -340,6 +702,7 @@ async fn after_hook(_: &Context, _: &Message, cmd_name: &str, error: Result<(),
 
 pub async fn run() {
     let _ = env::var("SECRET_KEY").expect("expect SECRET KEY in env var");
+    let _ = env::var("TOKEN_KEY").expect("expect TOKEN KEY in env var");
 
     let token = env::var("DISCORD_TOKEN").expect("invalid token");
     let app_id: u64 = env::var("APPLICATION_ID")
@@ -363,13 +726,19 @@ pub async fn run() {
         Err(e) => panic!("Couldn't get app info: {:?}", e),
     };
     let framework = StandardFramework::new()
-        .configure(|c| c.delimiter(';').prefix("=").owners(owners))
+        .configure(|c| c
+            .delimiter(ARG_DELIMITER)
+            // an empty static prefix drops the framework's default `~`, the prefix comes from `dynamic_prefix`
+            .prefix("")
+            .dynamic_prefix(dynamic_prefix)
+            .owners(owners))
         .before(before)
         .after(after_hook)
         .unrecognised_command(unknown_command)
         .group(&GENERAL_GROUP)
         .group(&NEWBINUSMAYA_GROUP)
         .group(&OLDBINUSMAYA_GROUP)
+        .group(&ADMIN_GROUP)
         .help(&HELP);
 
     let mut client = Client::builder(token)
@@ -392,6 +761,24 @@ pub async fn run() {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn guild_config_group_test() {
+        let mut guild_config = GuildConfig::new(1);
+        assert!(guild_config.is_group_enabled("OldBinusmaya"));
+
+        guild_config.set_group_enabled("OldBinusmaya", false);
+        guild_config.set_group_enabled("NewBinusmaya", false);
+        assert!(!guild_config.is_group_enabled("OldBinusmaya"));
+        assert!(!guild_config.is_group_enabled("newbinusmaya"));
+        assert!(guild_config.is_group_enabled("General"));
+
+        guild_config.set_group_enabled("OldBinusmaya", true);
+        guild_config.set_group_enabled("NewBinusmaya", true);
+        assert!(guild_config.is_group_enabled("OldBinusmaya"));
+        assert_eq!(guild_config.disabled_groups, None);
+    }
+
     #[tokio::test]
     async fn post_reminder_test() {
 
@@ -1,17 +1,30 @@
-use std::{error::Error, fmt::Display, str::FromStr, fs::read_to_string};
+use std::{error::Error, fmt::Display, ops::Add, str::FromStr, fs::read_to_string, sync::Arc};
 
-use csv_async::AsyncReaderBuilder;
-use futures::StreamExt;
+use chrono::{DateTime, Duration, Local};
+use csv_async::{AsyncReaderBuilder, AsyncWriterBuilder};
+use futures::{stream, StreamExt};
+use magic_crypt::MagicCryptTrait;
+use serde::{de::DeserializeOwned, Serialize};
 use serenity::{
-    builder::{CreateActionRow, CreateButton, CreateSelectMenu, CreateSelectMenuOption},
-    model::interactions::message_component::ButtonStyle,
+    builder::{CreateActionRow, CreateButton, CreateComponents, CreateSelectMenu, CreateSelectMenuOption},
+    framework::standard::{Args, CommandResult, Delimiter},
+    http::Http,
+    model::{
+        channel::Message,
+        id::{GuildId, RoleId, UserId},
+        interactions::{message_component::{ActionRowComponent, ButtonStyle, MessageComponentInteraction}, InteractionApplicationCommandCallbackDataFlags, InteractionResponseType},
+    },
+    prelude::{Context, SerenityError},
 };
 
-use crate::{consts::{OLDBINUSMAYA_USER_FILE, OLDBINUSMAYA_USER_DATA}, api::old_binusmaya_api::OldBinusmayaAPI};
+use tokio::fs::write;
 
-use super::discord::OldBinusmayaUserRecord;
+use crate::{consts::{ACTIVE_ACCOUNT, ACTIVE_ACCOUNT_FILE, ARG_DELIMITER, CLASS_CACHE, DEFAULT_ACCOUNT, DEFAULT_PREFIX, GUILD_CONFIG, GUILD_CONFIG_FILE, NEWBINUSMAYA_USER_DATA, NEWBINUSMAYA_USER_FILE, OLDBINUSMAYA_USER_FILE, OLDBINUSMAYA_USER_DATA, MAGIC_CRYPT, OLD_MAGIC_CRYPT, PASSWORD_CRYPT, PRIMARY_COLOR, TOKEN_CRYPT, USER_LANGUAGE, USER_LANGUAGE_FILE}, api::{dropbox_api, new_binusmaya_api::{Class, NewBinusmayaAPI}, old_binusmaya_api::{LoginError, OldBinusmayaAPI}}, crypto::{Cipher, CryptoError}, locale::Language};
 
-#[derive(PartialEq)]
+use super::markdown::split_text;
+use super::discord::{AccountKey, ActiveAccountRecord, GuildConfig, NewBinusmayaUserRecord, OldBinusmayaUserAuthInfo, OldBinusmayaUserRecord, UserLanguageRecord};
+
+#[derive(PartialEq, Debug)]
 pub enum Nav {
     Previous,
     Next,
@@ -51,10 +64,20 @@ impl FromStr for Nav {
 
 impl Nav {
     fn button(&self) -> CreateButton {
+        self.prefixed_button("", "", false)
+    }
+
+    /// A button whose custom id starts with `prefix`, so several navigations can share a message
+    fn prefixed_button(&self, prefix: &str, label: &str, disabled: bool) -> CreateButton {
         let mut btn = CreateButton::default();
-        btn.custom_id(self.to_string().to_ascii_lowercase());
-        btn.label(self);
+        btn.custom_id(format!("{}{}", prefix, self.to_string().to_ascii_lowercase()));
+        btn.label(match self {
+            _ if label.is_empty() => self.to_string(),
+            Self::Previous => format!("{} {}", self, label),
+            Self::Next => format!("{} {}", label, self),
+        });
         btn.style(ButtonStyle::Primary);
+        btn.disabled(disabled);
 
         btn
     }
@@ -66,51 +89,821 @@ impl Nav {
 
         ar
     }
+
+    pub fn prefixed_action_row(prefix: &str, label: &str, previous_disabled: bool, next_disabled: bool) -> CreateActionRow {
+        let mut ar = CreateActionRow::default();
+        ar.add_button(Nav::Previous.prefixed_button(prefix, label, previous_disabled));
+        ar.add_button(Nav::Next.prefixed_button(prefix, label, next_disabled));
+
+        ar
+    }
+
+    pub fn from_custom_id(prefix: &str, custom_id: &str) -> Option<Nav> {
+        custom_id.strip_prefix(prefix).and_then(|nav| Nav::from_str(nav).ok())
+    }
 }
 
-pub async fn update_cookie_all() {
-    let oldbinusmaya_content = read_to_string(OLDBINUSMAYA_USER_FILE).expect("Something's wrong when reading a file");
+/// Minutes until the buttons and menus of a command stop working, unless the guild configured another timeout
+pub const DEFAULT_INTERACTION_TIMEOUT: u64 = 5;
+pub const MAX_INTERACTION_TIMEOUT: u64 = 60;
+/// Keeps a page readable, well under the embed description limit
+const PAGE_LIMIT: usize = 2048;
+const PAGE_NAV_PREFIX: &str = "page";
 
-    let rdr = AsyncReaderBuilder::new()
-        .has_headers(false)
-        .create_deserializer(oldbinusmaya_content.as_bytes());
+/// Splits the text into pages at blank lines, so an entry of a list is kept on one page when it fits
+pub fn paginate(text: &str, limit: usize) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
 
-    let mut records = rdr.into_deserialize::<OldBinusmayaUserRecord>();
+    for block in text.split_inclusive("\n\n") {
+        if block.len() > limit {
+            if !page.is_empty() {
+                pages.push(std::mem::take(&mut page));
+            }
+            pages.extend(split_text(block, limit));
+            continue;
+        }
 
-    while let Some(record) = records.next().await {
-        let record = record.unwrap();
-        let old_binusmaya_api = OldBinusmayaAPI::login(&record.binusian_data, &record.user_credential).await;
+        if page.len() + block.len() > limit {
+            pages.push(std::mem::take(&mut page));
+        }
+        page.push_str(block);
+    }
+
+    if !page.is_empty() || pages.is_empty() {
+        pages.push(page);
+    }
+
+    pages
+}
+
+/// The pages of a long text, turned with its own `< page` and `page >` buttons
+pub struct Paginator {
+    pages: Vec<String>,
+    page: usize,
+    language: Language,
+}
+
+impl Paginator {
+    pub fn new(text: impl Display, language: Language) -> Self {
+        Paginator { pages: paginate(&text.to_string(), PAGE_LIMIT), page: 0, language }
+    }
+
+    pub fn page(&self) -> &str {
+        &self.pages[self.page]
+    }
+
+    pub fn is_paginated(&self) -> bool {
+        self.pages.len() > 1
+    }
+
+    pub fn footer(&self) -> String {
+        tr!(self.language, "pagination.footer", self.page + 1, self.pages.len())
+    }
+
+    pub fn action_row(&self, disabled: bool) -> CreateActionRow {
+        Nav::prefixed_action_row(PAGE_NAV_PREFIX, &tr!(self.language, "pagination.page"), disabled || self.page == 0, disabled || self.page + 1 == self.pages.len())
+    }
+
+    /// Adds the page buttons when there's more than one page
+    pub fn add_action_row<'a>(&self, c: &'a mut CreateComponents) -> &'a mut CreateComponents {
+        if self.is_paginated() {
+            c.add_action_row(self.action_row(false));
+        }
+
+        c
+    }
+
+    /// Turns the page if the custom id belongs to the page buttons
+    pub fn turn(&mut self, custom_id: &str) -> bool {
+        match Nav::from_custom_id(PAGE_NAV_PREFIX, custom_id) {
+            Some(Nav::Previous) => self.page = self.page.saturating_sub(1),
+            Some(Nav::Next) => self.page = (self.page + 1).min(self.pages.len() - 1),
+            None => return false,
+        }
+
+        true
+    }
+
+    /// Sends the pages in an embed, only the author of the message can turn them until the buttons time out
+    pub async fn send(mut self, ctx: &Context, msg: &Message, title: &str) -> Result<(), SerenityError> {
+        let mut message = msg.channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(title).description(self.page()).colour(PRIMARY_COLOR);
+                if self.is_paginated() {
+                    e.footer(|f| f.text(self.footer()));
+                }
+
+                e
+            });
+            m.components(|c| self.add_action_row(c))
+        }).await?;
+
+        if !self.is_paginated() {
+            return Ok(());
+        }
+
+        let mut cib = message.await_component_interactions(&ctx).timeout(interaction_timeout(msg.guild_id).await).await;
+        while let Some(mci) = cib.next().await {
+            if !is_invoker(ctx, msg, &mci).await? || !self.turn(&mci.data.custom_id) {
+                continue;
+            }
+
+            mci.create_interaction_response(&ctx, |r| {
+                r.kind(InteractionResponseType::UpdateMessage);
+                r.interaction_response_data(|d| {
+                    d.create_embed(|e| e
+                        .title(title)
+                        .description(self.page())
+                        .colour(PRIMARY_COLOR)
+                        .footer(|f| f.text(self.footer()))
+                    );
+                    d.components(|c| c.add_action_row(self.action_row(false)))
+                })
+            }).await?;
+        }
+
+        message.edit(&ctx, |m| m.components(|c| c.add_action_row(self.action_row(true)))).await?;
+
+        Ok(())
+    }
+}
+
+pub async fn interaction_timeout(guild_id: Option<GuildId>) -> std::time::Duration {
+    let minutes = match guild_id {
+        Some(guild_id) => GUILD_CONFIG
+            .lock()
+            .await
+            .get(guild_id.as_u64())
+            .and_then(|guild_config| guild_config.interaction_timeout)
+            .unwrap_or(DEFAULT_INTERACTION_TIMEOUT),
+        None => DEFAULT_INTERACTION_TIMEOUT,
+    };
+
+    std::time::Duration::from_secs(minutes * 60)
+}
+
+/// Waits for the author of the command to use a component of the message,
+/// the components are disabled if the author doesn't use them before the timeout
+pub async fn await_invoker_interaction(ctx: &Context, msg: &Message, message: &mut Message) -> Result<Option<Arc<MessageComponentInteraction>>, SerenityError> {
+    let mut cib = message.await_component_interactions(&ctx).timeout(interaction_timeout(msg.guild_id).await).await;
+    while let Some(mci) = cib.next().await {
+        if is_invoker(ctx, msg, &mci).await? {
+            return Ok(Some(mci));
+        }
+    }
+
+    disable_components(ctx, message).await?;
+
+    Ok(None)
+}
+
+/// Disables the buttons of the message and removes its select menus, link buttons keep working
+pub async fn disable_components(ctx: &Context, message: &mut Message) -> Result<(), SerenityError> {
+    // the components may have changed since the message was sent
+    *message = message.channel_id.message(&ctx.http, message.id).await?;
+
+    let rows: Vec<CreateActionRow> = message
+        .components
+        .iter()
+        .filter(|row| row.components.iter().any(|component| matches!(component, ActionRowComponent::Button(_))))
+        .map(|row| {
+            let mut ar = CreateActionRow::default();
+            for component in row.components.iter() {
+                if let ActionRowComponent::Button(button) = component {
+                    let mut btn = CreateButton::default();
+                    btn.style(button.style);
+                    if let Some(label) = &button.label {
+                        btn.label(label);
+                    }
+                    if let Some(emoji) = &button.emoji {
+                        btn.emoji(emoji.clone());
+                    }
+                    match (&button.url, &button.custom_id) {
+                        (Some(url), _) => btn.url(url),
+                        (None, Some(custom_id)) => btn.custom_id(custom_id).disabled(true),
+                        (None, None) => btn.disabled(true),
+                    };
+
+                    ar.add_button(btn);
+                }
+            }
+
+            ar
+        })
+        .collect();
+
+    message.edit(&ctx, |m| m.components(|c| c.set_action_rows(rows))).await
+}
+
+/// Tells anyone other than the author of the command that the buttons aren't theirs
+pub async fn is_invoker(ctx: &Context, msg: &Message, mci: &MessageComponentInteraction) -> Result<bool, SerenityError> {
+    if mci.user.id == msg.author.id {
+        return Ok(true);
+    }
+
+    // the reply is only seen by whoever clicked, so it's in their language
+    let language = language(mci.user.id.as_u64(), mci.guild_id).await;
+    mci.create_interaction_response(&ctx, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource);
+        r.interaction_response_data(|d| d
+            .content(tr!(language, "interaction.not_yours", msg.author.name))
+            .flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+        )
+    }).await?;
+
+    Ok(false)
+}
+
+#[derive(Debug)]
+pub enum CookieError {
+    NotRegistered,
+    LoginFailed,
+    /// Binusmaya couldn't be reached, the account isn't marked as failed
+    Unavailable,
+}
+
+impl Display for CookieError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotRegistered => write!(f, "User is not registered to old binusmaya"),
+            Self::LoginFailed => write!(f, "Failed to log in to old binusmaya"),
+            Self::Unavailable => write!(f, "Old binusmaya couldn't be reached"),
+        }
+    }
+}
+
+impl Error for CookieError {}
+
+/// Loads the registered old binusmaya users, their cookies are fetched when they're first needed
+pub async fn load_old_binusmaya_user_data() {
+    let mut user_records = read_records::<OldBinusmayaUserRecord>(OLDBINUSMAYA_USER_FILE).await;
+    let mut has_stale_password = false;
+
+    // legacy magic crypt passwords and passwords encrypted with the previous key are re-encrypted with the current key
+    for record in user_records.iter_mut() {
+        if let Ok((password, true)) = decrypt_password(&record.user_credential.password) {
+            has_stale_password = true;
+            record.user_credential.password = PASSWORD_CRYPT.encrypt(&password);
+        }
+    }
+
+    if has_stale_password {
+        match overwrite_records(OLDBINUSMAYA_USER_FILE, &user_records).await {
+            Ok(_) => println!("Re-encrypted passwords in {}", OLDBINUSMAYA_USER_FILE),
+            Err(e) => eprintln!("Failed to re-encrypt passwords: {:?}", e),
+        }
+    }
+
+    let mut user_data = OLDBINUSMAYA_USER_DATA.lock().await;
+    for record in user_records {
+        user_data.insert(
+            (record.member_id, record.account_name().to_string()),
+            OldBinusmayaUserAuthInfo {
+                cookie: None,
+                user_credential: record.user_credential,
+                binusian_data: record.binusian_data,
+                login_failed: false,
+            },
+        );
+    }
+}
+
+async fn mark_login_failed(http: &Http, account_key: &AccountKey) {
+    let is_first_failure = match OLDBINUSMAYA_USER_DATA.lock().await.get_mut(account_key) {
+        Some(user_auth_info) => !std::mem::replace(&mut user_auth_info.login_failed, true),
+        None => false,
+    };
+
+    if is_first_failure {
+        if let Ok(channel) = UserId(account_key.0).create_dm_channel(http).await {
+            let language = language(&account_key.0, None).await;
+            let _ = channel.id.send_message(http, |m| {
+                m.embed(|e| e
+                    .colour(PRIMARY_COLOR)
+                    .field(tr!(language, "account.old_login_failed.title"), tr!(language, "account.old_login_failed.body", account_key.1, DEFAULT_PREFIX, account_key.1), false)
+                )
+            }).await;
+        }
+    }
+}
+
+/// Logs the account in to old binusmaya again and stores the new cookie
+pub async fn refresh_cookie(http: &Http, account_key: &AccountKey) -> Result<OldBinusmayaAPI, CookieError> {
+    let (user_credential, binusian_data) = match OLDBINUSMAYA_USER_DATA.lock().await.get(account_key) {
+        Some(user_auth_info) => (user_auth_info.user_credential.clone(), user_auth_info.binusian_data.clone()),
+        None => return Err(CookieError::NotRegistered),
+    };
+
+    // a credential that can't be decrypted anymore means the record has to be registered again
+    if let Err(e) = decrypt_password(&user_credential.password) {
+        eprintln!("Failed to log in old binusmaya user {:?}: {}", account_key, e);
+        mark_login_failed(http, account_key).await;
+        return Err(CookieError::LoginFailed);
+    }
+
+    match OldBinusmayaAPI::login(&binusian_data, &user_credential).await {
+        Ok(old_binusmaya_api) => {
+            if let Some(user_auth_info) = OLDBINUSMAYA_USER_DATA.lock().await.get_mut(account_key) {
+                user_auth_info.cookie = Some(old_binusmaya_api.cookie.clone());
+                user_auth_info.login_failed = false;
+            }
+
+            Ok(old_binusmaya_api)
+        }
+        Err(e @ LoginError::Rejected(_)) => {
+            eprintln!("Failed to log in old binusmaya user {:?}: {}", account_key, e);
+            mark_login_failed(http, account_key).await;
+            Err(CookieError::LoginFailed)
+        }
+        // the credential may still be valid, so the next command tries to log in again
+        Err(e @ LoginError::Unavailable(_)) => {
+            eprintln!("Failed to log in old binusmaya user {:?}: {}", account_key, e);
+            Err(CookieError::Unavailable)
+        }
+    }
+}
+
+/// Returns the old binusmaya API of the account, the cookie is refreshed if the session has ended
+pub async fn get_old_binusmaya_api(http: &Http, account_key: &AccountKey) -> Result<OldBinusmayaAPI, CookieError> {
+    let cookie = match OLDBINUSMAYA_USER_DATA.lock().await.get(account_key) {
+        Some(user_auth_info) if user_auth_info.login_failed => return Err(CookieError::LoginFailed),
+        Some(user_auth_info) => user_auth_info.cookie.clone(),
+        None => return Err(CookieError::NotRegistered),
+    };
+
+    if let Some(cookie) = cookie {
+        let old_binusmaya_api = OldBinusmayaAPI { cookie };
+
+        if let Ok(session) = old_binusmaya_api.check_session().await {
+            if session.session_status != 0 {
+                return Ok(old_binusmaya_api);
+            }
+        }
+    }
+
+    refresh_cookie(http, account_key).await
+}
+
+/// Logs in every old binusmaya account in the background so their first command doesn't have to wait
+pub async fn warm_up_cookies(http: &Http) {
+    let account_keys: Vec<AccountKey> = OLDBINUSMAYA_USER_DATA.lock().await.keys().cloned().collect();
+
+    stream::iter(account_keys)
+        .for_each_concurrent(4, |account_key| async move {
+            let _ = refresh_cookie(http, &account_key).await;
+        })
+        .await;
+
+    println!("Old binusmaya cookies are ready");
+}
+
+pub async fn load_active_accounts() {
+    let active_account_records = read_records::<ActiveAccountRecord>(ACTIVE_ACCOUNT_FILE).await;
+    let mut active_accounts = ACTIVE_ACCOUNT.lock().await;
+
+    for record in active_account_records {
+        active_accounts.insert(record.member_id, record.account);
+    }
+}
+
+pub async fn load_guild_configs() {
+    let guild_configs = read_records::<GuildConfig>(GUILD_CONFIG_FILE).await;
+    let mut guild_config_data = GUILD_CONFIG.lock().await;
+
+    for guild_config in guild_configs {
+        guild_config_data.insert(guild_config.guild_id, guild_config);
+    }
+}
+
+/// Stores `guild_config` in memory and in the guild config file
+pub async fn save_guild_config(guild_config: GuildConfig) -> Result<(), Box<dyn Error>> {
+    let guild_configs: Vec<GuildConfig> = {
+        let mut guild_config_data = GUILD_CONFIG.lock().await;
+        guild_config_data.insert(guild_config.guild_id, guild_config);
+
+        guild_config_data.values().cloned().collect()
+    };
 
-        OLDBINUSMAYA_USER_DATA.try_lock().unwrap().insert(record.member_id, old_binusmaya_api.cookie);
+    overwrite_records(GUILD_CONFIG_FILE, &guild_configs).await
+}
+
+pub async fn load_user_languages() {
+    let user_language_records = read_records::<UserLanguageRecord>(USER_LANGUAGE_FILE).await;
+    let mut user_languages = USER_LANGUAGE.lock().await;
+
+    for record in user_language_records {
+        if let Ok(language) = record.language.parse::<Language>() {
+            user_languages.insert(record.member_id, language);
+        }
+    }
+}
+
+/// Stores the language of the user in memory and in the user language file, `None` follows the guild language again
+pub async fn save_user_language(user_id: u64, language: Option<Language>) -> Result<(), Box<dyn Error>> {
+    let user_language_records: Vec<UserLanguageRecord> = {
+        let mut user_languages = USER_LANGUAGE.lock().await;
+        match language {
+            Some(language) => user_languages.insert(user_id, language),
+            None => user_languages.remove(&user_id),
+        };
+
+        user_languages
+            .iter()
+            .map(|(member_id, language)| UserLanguageRecord { member_id: *member_id, language: language.code().to_string() })
+            .collect()
+    };
+
+    overwrite_records(USER_LANGUAGE_FILE, &user_language_records).await
+}
+
+/// The prefix set by the guild with `=config prefix`, the default prefix otherwise
+pub async fn guild_prefix(guild_id: Option<GuildId>) -> String {
+    let prefix = match guild_id {
+        Some(guild_id) => GUILD_CONFIG.lock().await.get(guild_id.as_u64()).and_then(|guild_config| guild_config.prefix.clone()),
+        None => None,
+    };
+
+    prefix.unwrap_or_else(|| DEFAULT_PREFIX.to_string())
+}
+
+/// The language set by the guild with `=config language`
+pub async fn guild_language(guild_id: Option<GuildId>) -> Option<Language> {
+    let guild_id = guild_id?;
+
+    GUILD_CONFIG
+        .lock()
+        .await
+        .get(guild_id.as_u64())
+        .and_then(|guild_config| guild_config.language.as_deref())
+        .and_then(|language| language.parse::<Language>().ok())
+}
+
+/// The language of the user, then the language of the guild, English otherwise
+pub async fn language(user_id: &u64, guild_id: Option<GuildId>) -> Language {
+    let user_language = USER_LANGUAGE.lock().await.get(user_id).copied();
+
+    match user_language {
+        Some(language) => language,
+        None => guild_language(guild_id).await.unwrap_or_default(),
+    }
+}
+
+/// The language to reply to the message in
+pub async fn msg_language(msg: &Message) -> Language {
+    language(msg.author.id.as_u64(), msg.guild_id).await
+}
+
+/// Replies to the message with a single field embed
+pub async fn reply(ctx: &Context, msg: &Message, name: &str, value: impl ToString) -> CommandResult {
+    msg.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e
+            .colour(PRIMARY_COLOR)
+            .field(name, value, false)
+        )
+    }).await?;
+
+    Ok(())
+}
+
+pub async fn active_account(user_id: &u64) -> String {
+    ACTIVE_ACCOUNT
+        .lock()
+        .await
+        .get(user_id)
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_ACCOUNT.to_string())
+}
+
+/// Returns the new binusmaya API of the account if its token hasn't expired
+pub async fn new_binusmaya_api(account_key: &AccountKey) -> Option<NewBinusmayaAPI> {
+    NEWBINUSMAYA_USER_DATA
+        .lock()
+        .await
+        .get(account_key)
+        .filter(|user_auth_info| user_auth_info.last_registered.add(Duration::weeks(52)) > Local::now())
+        .map(|user_auth_info| NewBinusmayaAPI { token: user_auth_info.auth.clone() })
+}
+
+/// Returns the new binusmaya API of the user's active account if its token hasn't expired
+pub async fn active_new_binusmaya_api(user_id: &u64) -> Option<NewBinusmayaAPI> {
+    new_binusmaya_api(&(*user_id, active_account(user_id).await)).await
+}
+
+/// The active classes of a user and when they were fetched
+pub type FetchedClasses = (DateTime<Local>, Vec<Class>);
+
+/// Returns the active classes of the user's active account, they're fetched again after 6 hours
+pub async fn cached_classes(user_id: &u64) -> Option<Vec<Class>> {
+    if let Some((fetched_at, classes)) = CLASS_CACHE.lock().await.get(user_id) {
+        if fetched_at.add(Duration::hours(6)) > Local::now() {
+            return Some(classes.clone());
+        }
+    }
+
+    let classes = active_new_binusmaya_api(user_id).await?.get_classes().await.ok()?.list;
+    CLASS_CACHE.lock().await.insert(*user_id, (Local::now(), classes.clone()));
+
+    Some(classes)
+}
+
+/// Whether the class belongs to the course, matched by its course code or a part of its course name
+pub fn matches_course(class: &Class, course: &Option<String>) -> bool {
+    match course {
+        Some(course) => {
+            class.course_code.eq_ignore_ascii_case(course)
+                || class.course_name.to_lowercase().contains(&course.to_lowercase())
+        }
+        None => true,
+    }
+}
+
+/// Whether the author of the message can manage the server or has the server's bot admin role
+pub async fn is_guild_admin(ctx: &Context, msg: &Message) -> bool {
+    let (guild_id, member) = match (msg.guild_id, msg.member(ctx).await) {
+        (Some(guild_id), Ok(member)) => (guild_id, member),
+        _ => return false,
+    };
+    let admin_role = GUILD_CONFIG
+        .lock()
+        .await
+        .get(guild_id.as_u64())
+        .and_then(|guild_config| guild_config.admin_role);
+
+    if admin_role.is_some_and(|role_id| member.roles.contains(&RoleId(role_id))) {
+        return true;
+    }
+
+    member
+        .permissions(ctx)
+        .await
+        .is_ok_and(|permissions| permissions.manage_guild())
+}
+
+/// The byte ranges of the whitespace separated tokens of the text
+fn token_ranges(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(token_start)) => {
+                ranges.push((token_start, i));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+
+    if let Some(token_start) = start {
+        ranges.push((token_start, text.len()));
+    }
+
+    ranges
+}
+
+/// Splits `--account [name]` or `--account=[name]` off the message, the rest of the message is kept as it was
+fn split_account_arg(message: &str) -> (Option<String>, String) {
+    let ranges = token_ranges(message);
+    let mut account: Option<String> = None;
+    let mut rest = String::new();
+    let mut kept_from = 0;
+    let mut i = 0;
+
+    while i < ranges.len() {
+        let (start, end) = ranges[i];
+        let token = &message[start..end];
+        let last = if token.eq("--account") && i + 1 < ranges.len() {
+            account = Some(message[ranges[i + 1].0..ranges[i + 1].1].to_string());
+            i + 1
+        } else if let Some(name) = token.strip_prefix("--account=") {
+            account = Some(name.to_string());
+            i
+        } else {
+            i += 1;
+            continue;
+        };
+
+        // the whitespace after the removed tokens goes with them
+        rest.push_str(&message[kept_from..start]);
+        kept_from = ranges.get(last + 1).map_or(message.len(), |(next_start, _)| *next_start);
+        i = last + 1;
+    }
+    rest.push_str(&message[kept_from..]);
+
+    (account, rest.trim().to_string())
+}
+
+/// Removes `--account [name]` from the message, returns the account name or `active_account` if it isn't given
+fn resolve_account_arg(message: &str, active_account: String) -> (String, Args) {
+    let (account, rest) = split_account_arg(message);
+
+    (account.unwrap_or(active_account), Args::new(&rest, &[Delimiter::Single(ARG_DELIMITER)]))
+}
+
+/// Removes `--account [name]` from the arguments, returns the account name or the user's active account if it isn't given
+pub async fn parse_account_arg(user_id: &u64, args: &Args) -> (String, Args) {
+    resolve_account_arg(args.message(), active_account(user_id).await)
+}
+
+/// Returns the new binusmaya API of the account, or replies why it can't be used
+pub async fn new_binusmaya_api_or_reply(ctx: &Context, msg: &Message, account: &str) -> serenity::Result<Option<NewBinusmayaAPI>> {
+    let account_key = (*msg.author.id.as_u64(), account.to_string());
+    let user_auth_info = NEWBINUSMAYA_USER_DATA
+        .lock()
+        .await
+        .get(&account_key)
+        .map(|user_auth_info| (user_auth_info.auth.clone(), user_auth_info.last_registered));
+
+    let language = msg_language(msg).await;
+    let prefix = guild_prefix(msg.guild_id).await;
+
+    match user_auth_info {
+        Some((auth, last_registered)) if last_registered.add(Duration::weeks(52)) > Local::now() => {
+            Ok(Some(NewBinusmayaAPI { token: auth }))
+        }
+        Some(_) => {
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.colour(PRIMARY_COLOR).field(
+                            tr!(language, "account.token_expired.title"),
+                            tr!(language, "account.token_expired.body", prefix, account),
+                            false,
+                        )
+                    })
+                })
+                .await?;
+
+            Ok(None)
+        }
+        None if account.eq(DEFAULT_ACCOUNT) => {
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.colour(PRIMARY_COLOR).field(
+                            tr!(language, "account.not_registered.title"),
+                            tr!(language, "account.not_registered.body", prefix),
+                            false,
+                        )
+                    })
+                })
+                .await?;
+
+            Ok(None)
+        }
+        None => {
+            msg.channel_id
+                .send_message(&ctx.http, |m| {
+                    m.embed(|e| {
+                        e.colour(PRIMARY_COLOR).field(
+                            tr!(language, "account.not_found.title"),
+                            tr!(language, "account.not_found.body", account, prefix),
+                            false,
+                        )
+                    })
+                })
+                .await?;
+
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the old binusmaya API of the account, or replies why it can't be used
+pub async fn old_binusmaya_api_or_reply(ctx: &Context, msg: &Message, account: &str) -> serenity::Result<Option<OldBinusmayaAPI>> {
+    let account_key = (*msg.author.id.as_u64(), account.to_string());
+    let language = msg_language(msg).await;
+    let prefix = guild_prefix(msg.guild_id).await;
+
+    match get_old_binusmaya_api(&ctx.http, &account_key).await {
+        Ok(old_binusmaya_api) => Ok(Some(old_binusmaya_api)),
+        Err(CookieError::NotRegistered) => {
+            msg.channel_id.send_message(&ctx.http, |m| {
+                m.embed(|e| e
+                    .colour(PRIMARY_COLOR)
+                    .field(tr!(language, "account.not_registered.title"), tr!(language, "account.old_not_registered.body", account, prefix), false)
+                )
+            }).await?;
+
+            Ok(None)
+        }
+        Err(CookieError::LoginFailed) => {
+            msg.channel_id.send_message(&ctx.http, |m| {
+                m.embed(|e| e
+                    .colour(PRIMARY_COLOR)
+                    .field(tr!(language, "account.login_failed.title"), tr!(language, "account.login_failed.body", prefix, account), false)
+                )
+            }).await?;
+
+            Ok(None)
+        }
+        Err(CookieError::Unavailable) => {
+            msg.channel_id.send_message(&ctx.http, |m| {
+                m.embed(|e| e
+                    .colour(PRIMARY_COLOR)
+                    .field(tr!(language, "account.login_failed.title"), tr!(language, "account.unavailable.body"), false)
+                )
+            }).await?;
+
+            Ok(None)
+        }
+    }
+}
+
+/// Bearer tokens registered before they were encrypted are stored in plaintext
+pub fn decrypt_token(auth: &str) -> Result<(String, bool), CryptoError> {
+    if Cipher::is_encrypted(auth) {
+        TOKEN_CRYPT.decrypt(auth)
+    } else {
+        Ok((auth.to_string(), true))
+    }
+}
+
+/// Passwords registered before versioned ciphertexts were introduced are magic crypt base64 strings
+pub fn decrypt_password(password: &str) -> Result<(String, bool), CryptoError> {
+    if Cipher::is_encrypted(password) {
+        return PASSWORD_CRYPT.decrypt(password);
     }
+
+    MAGIC_CRYPT
+        .decrypt_base64_to_string(password)
+        .or_else(|e| match OLD_MAGIC_CRYPT.as_ref() {
+            Some(old_magic_crypt) => old_magic_crypt.decrypt_base64_to_string(password),
+            None => Err(e),
+        })
+        .map(|password| (password, true))
+        .map_err(|e| CryptoError(e.to_string()))
+}
+
+pub async fn read_records<T: DeserializeOwned + 'static>(file_name: &str) -> Vec<T> {
+    let content = read_to_string(file_name).expect("Something's wrong when reading a file");
+
+    parse_records(file_name, &content).await
 }
 
-pub async fn update_cookie(user_id: &u64, mut old_binusmaya_api: OldBinusmayaAPI) -> OldBinusmayaAPI {
-    let oldbinusmaya_content = read_to_string(OLDBINUSMAYA_USER_FILE).expect("Something's wrong when reading a file");
-    
+/// Deserializes the records of the content of a user data file, malformed records are skipped
+pub async fn parse_records<T: DeserializeOwned + 'static>(file_name: &str, content: &str) -> Vec<T> {
+    // records may have optional trailing fields that were added later
     let rdr = AsyncReaderBuilder::new()
-    .has_headers(false)
-        .create_deserializer(oldbinusmaya_content.as_bytes());
+        .has_headers(false)
+        .flexible(true)
+        .create_deserializer(content.as_bytes());
+
+    let mut records = rdr.into_deserialize::<T>();
+    let mut user_records: Vec<T> = Vec::new();
 
-    let mut records = rdr.into_deserialize::<OldBinusmayaUserRecord>();
-    
     while let Some(record) = records.next().await {
-        let record = record.unwrap();
-        
-        if &record.member_id != user_id {
-            continue;
+        match record {
+            Ok(record) => user_records.push(record),
+            Err(e) => eprintln!("Skipping malformed record in {}: {}", file_name, e),
         }
+    }
+
+    user_records
+}
+
+/// Replaces the content of a user data file with `records` and uploads it to dropbox
+pub async fn overwrite_records<T: Serialize>(file_name: &str, records: &[T]) -> Result<(), Box<dyn Error>> {
+    let mut wtr = AsyncWriterBuilder::new()
+        .has_headers(false)
+        .create_serializer(vec![]);
 
-        old_binusmaya_api = OldBinusmayaAPI::login(&record.binusian_data, &record.user_credential).await;
+    for record in records {
+        wtr.serialize(record).await?;
     }
 
-    old_binusmaya_api
+    write(file_name, wtr.into_inner().await?).await?;
+    dropbox_api::upload_file(file_name.to_string()).await?;
+
+    Ok(())
+}
+
+/// Replaces every stored record of the account of `record` with `record`
+pub async fn replace_new_binusmaya_record(record: NewBinusmayaUserRecord) -> Result<(), Box<dyn Error>> {
+    let mut user_records = read_records::<NewBinusmayaUserRecord>(NEWBINUSMAYA_USER_FILE).await;
+    user_records.retain(|user_record| {
+        user_record.member_id != record.member_id || user_record.account_name() != record.account_name()
+    });
+    user_records.push(record);
+
+    overwrite_records(NEWBINUSMAYA_USER_FILE, &user_records).await
+}
+
+/// Discord rejects button labels over 80 characters
+pub fn button_label(name: &str) -> String {
+    if name.chars().count() > 80 {
+        format!("{}...", name.chars().take(77).collect::<String>())
+    } else {
+        name.to_string()
+    }
 }
 
-pub async fn select_menu(menu_options: Vec<CreateSelectMenuOption>) -> CreateSelectMenu {
+pub async fn select_menu(menu_options: Vec<CreateSelectMenuOption>, language: Language) -> CreateSelectMenu {
     let mut menu = CreateSelectMenu::default();
     menu.custom_id("academic_period_select");
-    menu.placeholder("No academic period selected");
+    menu.placeholder(tr!(language, "menu.academic_period.placeholder"));
     menu.options(|f| {
         for option in menu_options {
             f.add_option(option);
@@ -121,3 +914,59 @@ pub async fn select_menu(menu_options: Vec<CreateSelectMenuOption>) -> CreateSel
 
     menu
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_account_arg_test() {
+        let (account, mut args) = resolve_account_arg("2022-01-05 --account staff", DEFAULT_ACCOUNT.to_string());
+        assert_eq!(account, "staff");
+        assert_eq!(args.single::<String>().unwrap(), "2022-01-05");
+
+        let (account, args) = resolve_account_arg("--account=staff", DEFAULT_ACCOUNT.to_string());
+        assert_eq!(account, "staff");
+        assert!(args.is_empty());
+
+        let (account, _) = resolve_account_arg("2022-01-05", DEFAULT_ACCOUNT.to_string());
+        assert_eq!(account, DEFAULT_ACCOUNT);
+
+        let (account, _) = resolve_account_arg("", "staff".to_string());
+        assert_eq!(account, "staff");
+    }
+
+    #[test]
+    fn split_account_arg_test() {
+        assert_eq!(split_account_arg("2022-01-05 --account staff"), (Some("staff".to_string()), "2022-01-05".to_string()));
+        assert_eq!(split_account_arg("exam  schedule --account staff 2022"), (Some("staff".to_string()), "exam  schedule 2022".to_string()));
+        assert_eq!(split_account_arg("\"mid  term\" --account=staff --from 2022-01-05"), (Some("staff".to_string()), "\"mid  term\" --from 2022-01-05".to_string()));
+        assert_eq!(split_account_arg("a;b"), (None, "a;b".to_string()));
+        assert_eq!(split_account_arg("--account"), (None, "--account".to_string()));
+    }
+
+    #[test]
+    fn paginate_test() {
+        let text = "> a\n> b\n\n".repeat(3);
+
+        assert_eq!(paginate(&text, 20), vec!["> a\n> b\n\n> a\n> b\n\n", "> a\n> b\n\n"]);
+        assert_eq!(paginate("", 20), vec![""]);
+        assert_eq!(paginate(&"line\n".repeat(10), 30).len(), 2);
+    }
+
+    #[test]
+    fn paginator_turn_test() {
+        let mut paginator = Paginator::new("> entry\n\n".repeat(500), Language::English);
+
+        assert!(paginator.is_paginated());
+        assert!(!paginator.turn("<"));
+        assert!(paginator.turn("page<"));
+        assert_eq!(paginator.footer(), "page 1/3");
+        assert!(paginator.turn("page>"));
+        assert!(paginator.turn("page>"));
+        assert!(paginator.turn("page>"));
+        assert_eq!(paginator.footer(), "page 3/3");
+        assert_eq!(Paginator::new("entry", Language::Indonesian).footer(), "halaman 1/1");
+        assert_eq!(Nav::from_custom_id("", ">"), Some(Nav::Next));
+    }
+}
@@ -0,0 +1,380 @@
+use ego_tree::NodeRef;
+use scraper::{Html, Node};
+
+/// Discord rejects embed descriptions over 4096 characters
+pub const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+const CODE_BLOCK: &str = "```";
+
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    /// The item counter of every open list, `None` for unordered lists
+    lists: Vec<Option<usize>>,
+}
+
+fn escape(text: &str) -> String {
+    text.chars().fold(String::with_capacity(text.len()), |mut escaped, c| {
+        if "*_~`|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+
+        escaped
+    })
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut last_is_space = false;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_is_space {
+                collapsed.push(' ');
+            }
+            last_is_space = true;
+        } else {
+            collapsed.push(c);
+            last_is_space = false;
+        }
+    }
+
+    collapsed
+}
+
+impl Renderer {
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n')
+    }
+
+    fn new_line(&mut self) {
+        if !self.at_line_start() {
+            self.out.push('\n');
+        }
+    }
+
+    fn blank_line(&mut self) {
+        self.new_line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn render_children(&mut self, node: NodeRef<Node>) {
+        for child in node.children() {
+            self.render(child);
+        }
+    }
+
+    /// Renders the children on their own, used for text that has to fit in one line or be wrapped
+    fn render_inline(&mut self, node: NodeRef<Node>) -> String {
+        let mut renderer = Renderer::default();
+        renderer.render_children(node);
+
+        collapse_whitespace(&renderer.out).trim().to_string()
+    }
+
+    fn wrap(&mut self, node: NodeRef<Node>, marker: &str) {
+        let text = self.render_inline(node);
+        if !text.is_empty() {
+            self.out.push_str(&format!("{}{}{}", marker, text, marker));
+        }
+    }
+
+    fn render(&mut self, node: NodeRef<Node>) {
+        let element = match node.value() {
+            Node::Text(text) => {
+                let text = collapse_whitespace(text);
+                // whitespace at the start of a line is only indentation of the html
+                let text = if self.at_line_start() { text.trim_start() } else { &text };
+                self.out.push_str(&escape(text));
+
+                return;
+            }
+            Node::Element(element) => element,
+            _ => return self.render_children(node),
+        };
+
+        match element.name() {
+            "script" | "style" | "head" => {}
+            "br" => self.out.push('\n'),
+            "strong" | "b" => self.wrap(node, "**"),
+            "em" | "i" => self.wrap(node, "*"),
+            "u" => self.wrap(node, "__"),
+            "s" | "strike" | "del" => self.wrap(node, "~~"),
+            "code" => {
+                let text: String = node_text(node);
+                if !text.trim().is_empty() {
+                    self.out.push_str(&format!("`{}`", text.trim()));
+                }
+            }
+            "pre" => {
+                self.blank_line();
+                self.out.push_str(&format!("{}\n{}\n{}", CODE_BLOCK, node_text(node).trim_end(), CODE_BLOCK));
+                self.blank_line();
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.blank_line();
+                self.wrap(node, "**");
+                self.blank_line();
+            }
+            "p" | "div" | "blockquote" | "section" | "article" => {
+                self.blank_line();
+                self.render_children(node);
+                self.blank_line();
+            }
+            "hr" => {
+                self.blank_line();
+                self.out.push_str("───");
+                self.blank_line();
+            }
+            "a" => {
+                let text = self.render_inline(node);
+                match element.attr("href").map(str::trim) {
+                    Some(href) if href.starts_with("http") => {
+                        if text.is_empty() || text.eq(href) || escape(href).eq(&text) {
+                            self.out.push_str(href);
+                        } else {
+                            self.out.push_str(&format!("[{}]({})", text, href));
+                        }
+                    }
+                    _ => self.out.push_str(&text),
+                }
+            }
+            "img" => {
+                if let Some(src) = element.attr("src").filter(|src| src.starts_with("http")) {
+                    let alt = element.attr("alt").map(str::trim).filter(|alt| !alt.is_empty()).unwrap_or("Image");
+                    self.out.push_str(&format!("[{}]({})", escape(alt), src));
+                }
+            }
+            "ul" | "ol" => {
+                // nested lists stay attached to their item
+                let is_nested = !self.lists.is_empty();
+                if is_nested { self.new_line() } else { self.blank_line() }
+
+                self.lists.push(if element.name().eq("ol") { Some(0) } else { None });
+                self.render_children(node);
+                self.lists.pop();
+
+                if is_nested { self.new_line() } else { self.blank_line() }
+            }
+            "li" => {
+                self.new_line();
+                let depth = self.lists.len().max(1) - 1;
+                let bullet = match self.lists.last_mut() {
+                    Some(Some(counter)) => {
+                        *counter += 1;
+                        format!("{}. ", counter)
+                    }
+                    _ => "- ".to_string(),
+                };
+
+                self.out.push_str(&format!("{}{}", "  ".repeat(depth), bullet));
+                self.render_children(node);
+                self.new_line();
+            }
+            "table" => {
+                self.blank_line();
+                self.out.push_str(&render_table(node));
+                self.blank_line();
+            }
+            _ => self.render_children(node),
+        }
+    }
+}
+
+fn render_table(table: NodeRef<Node>) -> String {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut has_header = false;
+
+    for row in table.descendants().filter(|node| element_name(*node) == Some("tr")) {
+        let cells: Vec<NodeRef<Node>> = row
+            .children()
+            .filter(|node| matches!(element_name(*node), Some("td") | Some("th")))
+            .collect();
+
+        if rows.is_empty() {
+            has_header = cells.iter().all(|cell| element_name(*cell) == Some("th"));
+        }
+
+        rows.push(cells.into_iter().map(|cell| collapse_whitespace(&node_text(cell)).trim().to_string()).collect());
+    }
+
+    align_table(&rows, has_header)
+}
+
+fn element_name<'a>(node: NodeRef<'a, Node>) -> Option<&'a str> {
+    match node.value() {
+        Node::Element(element) => Some(element.name()),
+        _ => None,
+    }
+}
+
+/// The text of the node without any markdown, used in code blocks where markdown isn't rendered
+fn node_text(node: NodeRef<Node>) -> String {
+    node.descendants()
+        .filter_map(|node| match node.value() {
+            Node::Text(text) => Some(text.to_string()),
+            Node::Element(element) if element.name().eq("br") => Some("\n".to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Renders the rows as a code block with every column padded to its widest cell
+fn align_table(rows: &[Vec<String>], has_header: bool) -> String {
+    let column_count = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..column_count)
+        .map(|column| rows.iter().filter_map(|row| row.get(column)).map(|cell| cell.chars().count()).max().unwrap_or(0))
+        .collect();
+
+    let mut lines = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(column, width)| {
+                let cell = row.get(column).map(String::as_str).unwrap_or("");
+                format!("{}{}", cell, " ".repeat(width - cell.chars().count()))
+            })
+            .collect();
+        lines.push(cells.join(" | ").trim_end().to_string());
+
+        if i == 0 && has_header {
+            let separators: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+            lines.push(separators.join("-+-"));
+        }
+    }
+
+    format!("{}\n{}\n{}", CODE_BLOCK, lines.join("\n"), CODE_BLOCK)
+}
+
+/// Converts html, e.g. the content of an announcement, to Discord markdown
+pub fn html_to_markdown(html: &str) -> String {
+    let document = Html::parse_fragment(html);
+    let mut renderer = Renderer::default();
+    renderer.render(document.tree.root());
+
+    let mut markdown = String::new();
+    let mut blank_lines = 0;
+    for line in renderer.out.lines().map(str::trim_end) {
+        blank_lines = if line.is_empty() { blank_lines + 1 } else { 0 };
+        if blank_lines < 2 {
+            markdown.push_str(line);
+            markdown.push('\n');
+        }
+    }
+
+    markdown.trim().to_string()
+}
+
+/// Splits the text at line breaks into chunks of at most `limit` characters,
+/// a code block that is split is closed and opened again in the next chunk
+pub fn split_text(text: &str, limit: usize) -> Vec<String> {
+    // room for closing and reopening a code block
+    let limit = limit - CODE_BLOCK.len() - 1;
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    let mut in_code_block = false;
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let chars: Vec<char> = line.chars().collect();
+        if chars.is_empty() {
+            lines.push(String::new());
+        }
+        for part in chars.chunks(limit) {
+            lines.push(part.iter().collect());
+        }
+    }
+
+    for line in lines {
+        if !chunk.is_empty() && chunk.chars().count() + line.chars().count() + 1 > limit {
+            if in_code_block {
+                chunk.push_str(CODE_BLOCK);
+            }
+            chunks.push(chunk.trim_end().to_string());
+            chunk = if in_code_block { format!("{}\n", CODE_BLOCK) } else { String::new() };
+        }
+
+        if line.trim_start().starts_with(CODE_BLOCK) {
+            in_code_block = !in_code_block;
+        }
+
+        chunk.push_str(&line);
+        chunk.push('\n');
+    }
+
+    if !chunk.trim().is_empty() {
+        chunks.push(chunk.trim_end().to_string());
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // recorded from new binusmaya announcements
+    const SCHOLARSHIP_ANNOUNCEMENT: &str = r#"<p><span style="font-size: 11pt;">Dear <strong>BINUSIAN</strong>,</span></p>
+<p><span>Pendaftaran beasiswa dibuka sampai 30 April 2022 &ndash; info lengkap di <a href="https://binus.ac.id/scholarship/">sini</a>.</span></p>
+<ol>
+<li>Login ke <em>BINUSMAYA</em></li>
+<li>Isi formulir&nbsp;&amp; unggah dokumen</li>
+</ol>
+<ul>
+<li>IPK &ge; 3.00</li>
+</ul>"#;
+
+    const EXAM_ANNOUNCEMENT: &str = r#"<h3>Jadwal Ujian</h3>
+<table>
+<tbody>
+<tr><th>Course</th><th>Date</th></tr>
+<tr><td>COMP6047</td><td>12 May 2022</td></tr>
+<tr><td>CHAR6013 &#8211; Pancasila</td><td>13 May 2022</td></tr>
+</tbody>
+</table>
+<p><img src="https://binus.ac.id/exam.png" alt="Exam room" /></p>"#;
+
+    #[test]
+    fn html_to_markdown_test() {
+        assert_eq!(
+            html_to_markdown(SCHOLARSHIP_ANNOUNCEMENT),
+            "Dear **BINUSIAN**,\n\nPendaftaran beasiswa dibuka sampai 30 April 2022 – info lengkap di [sini](https://binus.ac.id/scholarship/).\n\n1. Login ke *BINUSMAYA*\n2. Isi formulir & unggah dokumen\n\n- IPK ≥ 3.00"
+        );
+    }
+
+    #[test]
+    fn table_test() {
+        assert_eq!(
+            html_to_markdown(EXAM_ANNOUNCEMENT),
+            "**Jadwal Ujian**\n\n```\nCourse               | Date\n---------------------+------------\nCOMP6047             | 12 May 2022\nCHAR6013 – Pancasila | 13 May 2022\n```\n\n[Exam room](https://binus.ac.id/exam.png)"
+        );
+    }
+
+    #[test]
+    fn nested_list_test() {
+        assert_eq!(html_to_markdown("<ul><li>Tugas<ol><li>Laporan</li><li>Video</li></ol></li><li>Kuis</li></ul>"), "- Tugas\n  1. Laporan\n  2. Video\n- Kuis");
+    }
+
+    #[test]
+    fn escape_test() {
+        assert_eq!(html_to_markdown("<p>snake_case *star*</p>"), "snake\\_case \\*star\\*");
+        assert_eq!(html_to_markdown(r#"<a href="https://binus.ac.id">https://binus.ac.id</a>"#), "https://binus.ac.id");
+    }
+
+    #[test]
+    fn split_text_test() {
+        let text = (0..100).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
+        let chunks = split_text(&text, 100);
+
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 100));
+        assert_eq!(chunks.join("\n"), text);
+
+        let code = format!("```\n{}\n```", text);
+        let chunks = split_text(&code, 100);
+        assert!(chunks.iter().all(|chunk| chunk.starts_with("```") && chunk.ends_with("```")));
+    }
+}
@@ -1,3 +1,10 @@
+pub mod archive;
+pub mod class_subscription;
+pub mod class_sync;
 pub mod commands;
 pub mod discord;
-pub mod helper;
\ No newline at end of file
+pub mod helper;
+pub mod markdown;
+pub mod render;
+pub mod scheduler;
+pub mod xp_leaderboard;
\ No newline at end of file
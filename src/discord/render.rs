@@ -0,0 +1,404 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serenity::builder::CreateEmbed;
+
+use crate::{
+    api::{
+        new_binusmaya_api::{Announcement, ClassVec, Lecturer, OngoingClasses, ResourceList, Schedule, SessionDetails, SimpleLecturer, SubTopics, UpcomingClass},
+        old_binusmaya_api::{AssignmentList, ComServList, SATPoints},
+    },
+    consts::{NEW_BINUSMAYA, PRIMARY_COLOR},
+    locale::{format_api_datetime, format_date, Language},
+};
+
+pub fn session_url(class_id: &str, session_id: &str) -> String {
+    format!("{}/lms/course/{}/session/{}", NEW_BINUSMAYA, class_id, session_id)
+}
+
+pub fn forum_url(class_id: &str, session_id: &str) -> String {
+    format!("{}/lms/course/{}/forum/{}", NEW_BINUSMAYA, class_id, session_id)
+}
+
+/// A `> name: **value**` line of an entry in a list
+fn line(name: String, value: impl std::fmt::Display) -> String {
+    format!("> {}: **{}**\n", name, value)
+}
+
+fn session_link(language: Language, class_id: &str, session_id: &str) -> String {
+    format!("> [{}]({})\n", tr!(language, "session.link"), session_url(class_id, session_id))
+}
+
+fn join_names<'a>(names: impl Iterator<Item = &'a str>, language: Language) -> String {
+    let names: Vec<&str> = names.filter(|name| !name.is_empty()).collect();
+
+    if names.is_empty() {
+        tr!(language, "lecturers.unknown")
+    } else {
+        names.join(", ")
+    }
+}
+
+pub fn lecturer_names(lecturers: &[Lecturer], language: Language) -> String {
+    join_names(lecturers.iter().map(|lecturer| lecturer.name.as_str()), language)
+}
+
+pub fn simple_lecturer_names(lecturers: &[SimpleLecturer], language: Language) -> String {
+    join_names(lecturers.iter().map(|lecturer| lecturer.name.as_str()), language)
+}
+
+pub fn schedule(schedule: &Schedule, language: Language) -> String {
+    let mut description = format!("{}\n", tr!(language, "schedule.sessions", schedule.schedule.len()));
+
+    for class in &schedule.schedule {
+        description.push_str(&line(tr!(language, "schedule.class_title"), &class.title));
+        description.push_str(&line(tr!(language, "schedule.subject"), &class.content));
+        description.push_str(&line(tr!(language, "schedule.start"), format_api_datetime(language, &class.date_start)));
+        description.push_str(&line(tr!(language, "schedule.end"), format_api_datetime(language, &class.date_end)));
+        description.push_str(&line(tr!(language, "session.session"), class.custom_param.session_number));
+        description.push_str(&line(tr!(language, "session.class_delivery_mode"), &class.class_delivery_mode));
+        if let Some(location) = &class.location_value {
+            description.push_str(&line(tr!(language, "schedule.location"), location));
+        }
+        description.push_str(&session_link(language, &class.custom_param.class_id, &class.custom_param.class_session_id));
+        description.push('\n');
+    }
+
+    description
+}
+
+pub fn resources(resources: &ResourceList, language: Language) -> String {
+    let mut description = String::new();
+
+    for resource in &resources.list {
+        let duration = match resource.duration.as_deref().and_then(|duration| duration.parse::<u32>().ok()) {
+            Some(seconds) => (seconds / 60).to_string(),
+            None => "?".to_string(),
+        };
+        let progress_status = if resource.is_completed() {
+            tr!(language, "progress.completed")
+        } else if resource.progress_status == 1 {
+            tr!(language, "progress.in_progress")
+        } else {
+            tr!(language, "progress.not_started")
+        };
+
+        description.push_str(&line(tr!(language, "resource.name"), &resource.name));
+        description.push_str(&line(tr!(language, "resource.duration"), tr!(language, "resource.minutes", duration)));
+        description.push_str(&line(tr!(language, "resource.type"), &resource.resource_type));
+        description.push_str(&line(tr!(language, "progress.status"), progress_status));
+        description.push('\n');
+    }
+
+    description
+}
+
+pub fn subtopics(subtopics: &SubTopics) -> String {
+    subtopics.subtopics.iter().map(|topic| format!("- {}\n", topic)).collect()
+}
+
+/// The zoom link, lecturers, subtopics and resources of a session
+pub fn session_details(session_details: &SessionDetails, language: Language) -> String {
+    format!("**{}**\n{}\n\n**{}**\n{}\n\n**{}**\n{}\n**{}**\n{}",
+        tr!(language, "session.zoom_link"),
+        session_details.join_url.clone().unwrap_or_else(|| tr!(language, "session.no_link")),
+        tr!(language, "session.lecturers"),
+        lecturer_names(&session_details.lecturers, language),
+        tr!(language, "session.subtopics"),
+        subtopics(&session_details.course_sub_topic),
+        tr!(language, "session.resources"),
+        resources(&session_details.resources, language)
+    )
+}
+
+pub fn classes(classes: &ClassVec, language: Language) -> String {
+    if classes.list.is_empty() {
+        return tr!(language, "classes.none");
+    }
+
+    let mut description = String::new();
+
+    for class in &classes.list {
+        description.push_str(&line(tr!(language, "class.class_code"), &class.class_Code));
+        description.push_str(&line(tr!(language, "class.course_code"), &class.course_code));
+        description.push_str(&line(tr!(language, "class.course_name"), &class.course_name));
+        description.push_str(&line(tr!(language, "class.component"), &class.ssr_component));
+        // the id is used by other commands, so it's in a code span that's easy to copy
+        description.push_str(&format!("> {}: `{}`\n\n", tr!(language, "class.class_id"), class.class_id));
+    }
+
+    description
+}
+
+/// The time left of each session is counted from `now`
+pub fn ongoing_classes(ongoing_classes: &OngoingClasses, language: Language, now: NaiveDateTime) -> String {
+    let mut description = format!("{}\n", tr!(language, "ongoing.count", ongoing_classes.ongoing_classes.len()));
+
+    for ongoing_class in &ongoing_classes.ongoing_classes {
+        let progress_status = if ongoing_class.resources.is_empty() {
+            tr!(language, "progress.completed")
+        } else {
+            tr!(language, "progress.incomplete")
+        };
+        let time_left = match NaiveDateTime::parse_from_str(&ongoing_class.date_end, "%FT%X") {
+            Ok(date_end) => (date_end - now).num_minutes().to_string(),
+            Err(_) => "?".to_string(),
+        };
+
+        description.push_str(&line(tr!(language, "class.component"), &ongoing_class.course_component));
+        description.push_str(&line(tr!(language, "class.course_name"), &ongoing_class.course_name));
+        description.push_str(&line(tr!(language, "ongoing.time_left"), tr!(language, "resource.minutes", time_left)));
+        description.push_str(&line(tr!(language, "session.session"), ongoing_class.session_number));
+        description.push_str(&line(tr!(language, "session.lecturers"), simple_lecturer_names(&ongoing_class.lecturers, language)));
+        description.push_str(&line(tr!(language, "session.delivery_mode"), &ongoing_class.delivery_mode));
+        description.push_str(&line(tr!(language, "progress.status"), progress_status));
+        description.push_str(&session_link(language, &ongoing_class.class_id, &ongoing_class.session_id));
+        description.push('\n');
+    }
+
+    description
+}
+
+pub fn upcoming_class(upcoming_class: &UpcomingClass, language: Language) -> String {
+    let mut description = format!("**{}**\n{}\n\n**{}**\n",
+        tr!(language, "session.zoom_link"),
+        upcoming_class.join_url.clone().unwrap_or_else(|| tr!(language, "session.no_link")),
+        tr!(language, "upcoming.session_info")
+    );
+
+    description.push_str(&line(tr!(language, "class.component"), &upcoming_class.course_component));
+    description.push_str(&line(tr!(language, "class.course_name"), &upcoming_class.course_name));
+    description.push_str(&line(tr!(language, "upcoming.time_start"), format_api_datetime(language, &upcoming_class.date_start)));
+    description.push_str(&line(tr!(language, "session.session"), upcoming_class.session_number));
+    description.push_str(&line(tr!(language, "session.lecturers"), simple_lecturer_names(&upcoming_class.lecturers, language)));
+    description.push_str(&line(tr!(language, "session.delivery_mode"), &upcoming_class.delivery_mode));
+    description.push_str(&session_link(language, &upcoming_class.class_id, &upcoming_class.session_id));
+
+    description
+}
+
+pub fn upcoming_embed<'a>(e: &'a mut CreateEmbed, upcoming_class: Option<&UpcomingClass>, language: Language) -> &'a mut CreateEmbed {
+    e.title(tr!(language, "upcoming.title"))
+        .description(match upcoming_class {
+            Some(upcoming_class) => self::upcoming_class(upcoming_class, language),
+            None => tr!(language, "upcoming.none"),
+        })
+        .colour(PRIMARY_COLOR)
+}
+
+pub fn assignments(assignments: &AssignmentList, language: Language) -> String {
+    if assignments.assignments.is_empty() {
+        return tr!(language, "assignment.none");
+    }
+
+    assignments
+        .assignments
+        .iter()
+        .map(|assignment| format!("{}{}\n",
+            line(tr!(language, "assignment.title"), &assignment.title),
+            line(tr!(language, "assignment.due"), format!("{} {}", assignment.deadline_duration, assignment.deadline_time))
+        ))
+        .collect()
+}
+
+pub fn sat_points(sat_points: &SATPoints, language: Language) -> String {
+    sat_points
+        .sat_points
+        .iter()
+        .map(|sat_point| format!("{} - {}\n", sat_point.activity_type, tr!(language, "sat.points", sat_point.points)))
+        .collect()
+}
+
+pub fn sat_embed<'a>(e: &'a mut CreateEmbed, sat_points: &SATPoints, language: Language) -> &'a mut CreateEmbed {
+    e.colour(PRIMARY_COLOR).field(tr!(language, "sat.title"), self::sat_points(sat_points, language), true)
+}
+
+pub fn community_service(community_service: &ComServList, language: Language) -> String {
+    community_service
+        .list
+        .iter()
+        .map(|comserv| format!("{} - {}\n", comserv.community_service_type, tr!(language, "comserv.hours", comserv.points)))
+        .collect()
+}
+
+pub fn community_service_embed<'a>(e: &'a mut CreateEmbed, community_service: &ComServList, language: Language) -> &'a mut CreateEmbed {
+    e.colour(PRIMARY_COLOR).field(tr!(language, "comserv.title"), self::community_service(community_service, language), true)
+}
+
+/// The date of a `2022-03-01T00:00:00` announcement date, kept as it is if it isn't one
+fn announcement_date(date: &str, language: Language) -> String {
+    let date = date.split('T').next().unwrap_or_default();
+
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(date) => format_date(language, date),
+        Err(_) => date.to_string(),
+    }
+}
+
+/// Numbers the announcements from `first_number`, unread ones are marked with a blue circle
+pub fn announcements(announcements: &[Announcement], first_number: usize, language: Language) -> String {
+    if announcements.is_empty() {
+        return tr!(language, "announcement.none");
+    }
+
+    announcements
+        .iter()
+        .enumerate()
+        .map(|(i, announcement)| format!(
+            "{}. {}**{}**\n{} | {}\n",
+            first_number + i,
+            if announcement.is_read { "" } else { "🔵 " },
+            announcement.title,
+            announcement.academic_career_desc,
+            announcement_date(&announcement.start_date, language)
+        ))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn lecturer_names_test() {
+        let lecturer = |name: &str| Lecturer {
+            id: String::new(),
+            name: name.to_string(),
+            picture_url: None,
+            role: String::new(),
+            user_code: String::new(),
+        };
+
+        assert_eq!(lecturer_names(&[], Language::English), "Unknown");
+        assert_eq!(lecturer_names(&[lecturer("A"), lecturer(""), lecturer("B")], Language::Indonesian), "A, B");
+    }
+
+    fn schedule_fixture() -> Schedule {
+        serde_json::from_value(json!({
+            "Schedule": [{
+                "dateStart": "2022-01-05T07:20:00",
+                "dateEnd": "2022-01-05T09:00:00",
+                "title": "LA01",
+                "content": "Algorithm and Programming",
+                "locationValue": "Anggrek 612",
+                "customParam": { "classId": "class-1", "classSessionId": "session-1", "sessionNumber": 3 },
+                "classDeliveryMode": "F2F"
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn schedule_test() {
+        assert_eq!(schedule(&schedule_fixture(), Language::English), "**1 Session(s)**
+> Class Title: **LA01**
+> Subject: **Algorithm and Programming**
+> Start: **Wednesday, 5 January 2022 07:20**
+> End: **Wednesday, 5 January 2022 09:00**
+> Session: **3**
+> Class Delivery Mode: **F2F**
+> Location: **Anggrek 612**
+> [Session link](https://newbinusmaya.binus.ac.id/lms/course/class-1/session/session-1)
+
+");
+        assert!(schedule(&schedule_fixture(), Language::Indonesian).contains("> Mulai: **Rabu, 5 Januari 2022 07:20**\n"));
+    }
+
+    #[test]
+    fn resources_test() {
+        let resources_fixture: ResourceList = serde_json::from_value(json!([
+            { "duration": "900", "id": "1", "name": "Slides", "progressStamp": 1, "progressStatus": 0, "resourceType": "PDF", "type": null, "url": null },
+            { "duration": null, "id": "2", "name": "Forum", "progressStamp": 0, "progressStatus": 1, "resourceType": "Forum", "type": null, "url": null }
+        ]))
+        .unwrap();
+
+        assert_eq!(resources(&resources_fixture, Language::English), "> Name: **Slides**
+> Duration: **15 min**
+> Type: **PDF**
+> Status: **Completed**
+
+> Name: **Forum**
+> Duration: **? min**
+> Type: **Forum**
+> Status: **In progress**
+
+");
+    }
+
+    #[test]
+    fn classes_test() {
+        let classes_fixture: ClassVec = serde_json::from_value(json!([
+            { "classCode": "LA01", "classId": "class-1", "courseCode": "COMP6047", "courseName": "Algorithm and Programming", "ssrComponent": "LEC" }
+        ]))
+        .unwrap();
+
+        assert_eq!(classes(&classes_fixture, Language::Indonesian), "> Kode kelas: **LA01**
+> Kode mata kuliah: **COMP6047**
+> Nama mata kuliah: **Algorithm and Programming**
+> Komponen kelas: **LEC**
+> Id kelas: `class-1`
+
+");
+        assert_eq!(classes(&ClassVec { list: Vec::new() }, Language::English), "No active class");
+    }
+
+    #[test]
+    fn ongoing_classes_test() {
+        let ongoing_fixture: OngoingClasses = serde_json::from_value(json!([{
+            "classId": "class-1",
+            "courseComponent": "LEC",
+            "courseName": "Algorithm and Programming",
+            "dateEnd": "2022-01-05T09:00:00",
+            "deliveryMode": "VC",
+            "id": "1",
+            "lecturers": [{ "id": "1", "name": "Jane Doe" }],
+            "meetingStart": "2022-01-05T07:20:00",
+            "resources": [],
+            "sessionId": "session-1",
+            "sessionNumber": 3
+        }]))
+        .unwrap();
+        let now = NaiveDateTime::parse_from_str("2022-01-05T08:00:00", "%FT%X").unwrap();
+
+        assert_eq!(ongoing_classes(&ongoing_fixture, Language::English, now), "**1 Ongoing Session(s)**
+> Class component: **LEC**
+> Course name: **Algorithm and Programming**
+> Time Left: **60 min**
+> Session: **3**
+> Lecturers: **Jane Doe**
+> Delivery Mode: **VC**
+> Status: **Completed**
+> [Session link](https://newbinusmaya.binus.ac.id/lms/course/class-1/session/session-1)
+
+");
+    }
+
+    #[test]
+    fn upcoming_class_test() {
+        let upcoming_fixture: UpcomingClass = serde_json::from_value(json!({
+            "classId": "class-1",
+            "courseComponent": "LEC",
+            "courseName": "Algorithm and Programming",
+            "dateEnd": "2022-01-05T09:00:00",
+            "dateStart": "2022-01-05T07:20:00",
+            "deliveryMode": "VC",
+            "joinUrl": null,
+            "lecturers": [],
+            "sessionId": "session-1",
+            "sessionNumber": 3
+        }))
+        .unwrap();
+
+        assert_eq!(upcoming_class(&upcoming_fixture, Language::Indonesian), "**Tautan Zoom Kelas**
+Tidak ada tautan
+
+**Info Sesi**
+> Komponen kelas: **LEC**
+> Nama mata kuliah: **Algorithm and Programming**
+> Waktu Mulai: **Rabu, 5 Januari 2022 07:20**
+> Sesi: **3**
+> Dosen: **Tidak diketahui**
+> Mode Perkuliahan: **VC**
+> [Tautan sesi](https://newbinusmaya.binus.ac.id/lms/course/class-1/session/session-1)
+");
+    }
+}
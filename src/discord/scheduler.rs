@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local};
+
+use crate::consts::TASK_STATUS;
+
+pub const DAILY_TASK: &str = "Daily progress update";
+pub const TOKEN_REFRESH_TASK: &str = "Token refresh";
+pub const CLASS_SYNC_TASK: &str = "Class role sync";
+pub const CLASS_SUBSCRIPTION_TASK: &str = "Class subscriptions";
+pub const XP_MOVERS_TASK: &str = "XP movers";
+
+/// The background tasks in the order `=admin tasks` lists them
+pub const TASKS: [&str; 5] = [DAILY_TASK, TOKEN_REFRESH_TASK, CLASS_SYNC_TASK, CLASS_SUBSCRIPTION_TASK, XP_MOVERS_TASK];
+
+#[derive(Clone, Debug, Default)]
+pub struct TaskStatus {
+    pub running: bool,
+    pub runs: u32,
+    pub last_started: Option<DateTime<Local>>,
+    pub last_finished: Option<DateTime<Local>>,
+}
+
+/// Marks the task as running, returns `false` if it's already running
+pub async fn start_task(task: &'static str) -> bool {
+    let mut task_status = TASK_STATUS.lock().await;
+    let status = task_status.entry(task).or_default();
+
+    if status.running {
+        return false;
+    }
+
+    status.running = true;
+    status.last_started = Some(Local::now());
+
+    true
+}
+
+pub async fn finish_task(task: &'static str) {
+    if let Some(status) = TASK_STATUS.lock().await.get_mut(task) {
+        status.running = false;
+        status.runs += 1;
+        status.last_finished = Some(Local::now());
+    }
+}
+
+pub async fn is_task_running(task: &'static str) -> bool {
+    matches!(TASK_STATUS.lock().await.get(task), Some(status) if status.running)
+}
+
+/// A line per task with whether it's running and when it last finished
+pub fn describe_tasks(task_status: &HashMap<&'static str, TaskStatus>) -> String {
+    TASKS
+        .iter()
+        .map(|task| {
+            let status = task_status.get(task).cloned().unwrap_or_default();
+            let state = match status.last_started {
+                Some(last_started) if status.running => format!("running since {}", last_started.format("%F %T")),
+                _ => "idle".to_string(),
+            };
+            let last_finished = status.last_finished.map_or("never".to_string(), |last_finished| last_finished.format("%F %T").to_string());
+
+            format!("**{}**: {}, {} run(s), last finished {}\n", task, state, status.runs, last_finished)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDateTime, TimeZone};
+
+    #[test]
+    fn describe_tasks_test() {
+        let time = Local.from_local_datetime(&NaiveDateTime::parse_from_str("2022-01-05T07:20:00", "%FT%X").unwrap()).unwrap();
+        let task_status = HashMap::from([
+            (DAILY_TASK, TaskStatus { running: true, runs: 2, last_started: Some(time), last_finished: Some(time) }),
+            (CLASS_SYNC_TASK, TaskStatus { running: false, runs: 1, last_started: Some(time), last_finished: Some(time) }),
+        ]);
+
+        assert_eq!(describe_tasks(&task_status), "**Daily progress update**: running since 2022-01-05 07:20:00, 2 run(s), last finished 2022-01-05 07:20:00
+**Token refresh**: idle, 0 run(s), last finished never
+**Class role sync**: idle, 1 run(s), last finished 2022-01-05 07:20:00
+**Class subscriptions**: idle, 0 run(s), last finished never
+**XP movers**: idle, 0 run(s), last finished never
+");
+    }
+}
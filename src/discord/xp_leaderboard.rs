@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use serenity::{model::prelude::*, prelude::*, utils::MessageBuilder};
+
+use crate::{
+    consts::{GUILD_CONFIG, LEADERBOARD_FILE, PRIMARY_COLOR, XP_SNAPSHOT_FILE},
+    discord::{helper::{active_new_binusmaya_api, guild_language, guild_prefix, overwrite_records, read_records}, scheduler::{finish_task, start_task, XP_MOVERS_TASK}},
+};
+
+/// How many members are shown in the leaderboard and the weekly movers
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// A user who agreed to show their XP in the leaderboard of the servers they're in
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderboardRecord {
+    pub member_id: u64,
+}
+
+/// The XP of a leaderboard member at the last weekly update, `None` if it has never been fetched
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct XpSnapshotRecord {
+    pub member_id: u64,
+    pub xp_point: Option<f32>,
+    pub recorded_at: DateTime<Local>,
+}
+
+pub async fn leaderboard_members() -> Vec<u64> {
+    read_records::<LeaderboardRecord>(LEADERBOARD_FILE)
+        .await
+        .into_iter()
+        .map(|record| record.member_id)
+        .collect()
+}
+
+/// Returns the XP of the user's active new binusmaya account
+pub async fn member_xp(member_id: &u64) -> Option<f32> {
+    let binusmaya_api = active_new_binusmaya_api(member_id).await?;
+
+    match binusmaya_api.get_user_profile().await {
+        Ok(user_profile) => Some(user_profile.xP_point),
+        Err(e) => {
+            eprintln!("Failed to get the profile of {}: {:?}", member_id, e);
+            None
+        }
+    }
+}
+
+/// Sorts the members by their value, the highest first, and keeps the top of the leaderboard
+pub fn rank(mut values: Vec<(u64, f32)>) -> Vec<(u64, f32)> {
+    values.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    values.truncate(LEADERBOARD_SIZE);
+
+    values
+}
+
+pub fn describe_ranking(ranking: &[(u64, f32)], sign: bool) -> String {
+    let mut content = MessageBuilder::new();
+
+    for (position, (member_id, value)) in ranking.iter().enumerate() {
+        let value = if sign { format!("{:+}", value) } else { value.to_string() };
+
+        content
+            .push_bold(format!("{}. ", position + 1))
+            .mention(&UserId(*member_id))
+            .push_line(format!(" - {} XP", value));
+    }
+
+    content.build()
+}
+
+async fn in_guild(ctx: &Context, guild_id: u64, member_id: u64) -> bool {
+    GuildId(guild_id).member(ctx, member_id).await.is_ok()
+}
+
+async fn post_movers(ctx: &Context, xp_gains: &[(u64, f32)]) {
+    let reminder_channels: Vec<(u64, u64)> = GUILD_CONFIG
+        .lock()
+        .await
+        .values()
+        .filter_map(|guild_config| guild_config.reminder_channel.map(|channel_id| (guild_config.guild_id, channel_id)))
+        .collect();
+
+    for (guild_id, channel_id) in reminder_channels {
+        let mut guild_gains = Vec::new();
+        for (member_id, gain) in xp_gains.iter().filter(|(_, gain)| *gain > 0.0) {
+            if in_guild(ctx, guild_id, *member_id).await {
+                guild_gains.push((*member_id, *gain));
+            }
+        }
+
+        if guild_gains.is_empty() {
+            continue;
+        }
+
+        let movers = describe_ranking(&rank(guild_gains), true);
+        let language = guild_language(Some(GuildId(guild_id))).await.unwrap_or_default();
+        let prefix = guild_prefix(Some(GuildId(guild_id))).await;
+        let result = ChannelId(channel_id).send_message(&ctx.http, |m| {
+            m.embed(|e| e
+                .title(tr!(language, "leaderboard.movers.title"))
+                .colour(PRIMARY_COLOR)
+                .description(movers)
+                .footer(|f| f.text(tr!(language, "leaderboard.movers.footer", prefix)))
+            )
+        }).await;
+
+        if let Err(e) = result {
+            eprintln!("Failed to post the XP movers to {}: {:?}", channel_id, e);
+        }
+    }
+}
+
+/// The XP gained since the previous snapshot and the new snapshot, members whose XP couldn't be fetched keep their previous XP
+fn next_snapshot(previous_xp: &HashMap<u64, f32>, current_xp: &[(u64, Option<f32>)], recorded_at: DateTime<Local>) -> (Vec<(u64, f32)>, Vec<XpSnapshotRecord>) {
+    let mut xp_gains = Vec::new();
+    let mut snapshots = Vec::new();
+
+    for (member_id, xp_point) in current_xp {
+        let previous = previous_xp.get(member_id).copied();
+        if let (Some(xp_point), Some(previous)) = (xp_point, previous) {
+            xp_gains.push((*member_id, xp_point - previous));
+        }
+
+        snapshots.push(XpSnapshotRecord { member_id: *member_id, xp_point: xp_point.or(previous), recorded_at });
+    }
+
+    (xp_gains, snapshots)
+}
+
+/// Posts the members who gained the most XP in the last week to the reminder channel of every guild
+pub async fn xp_movers_event(ctx: &Context) {
+    loop {
+        start_task(XP_MOVERS_TASK).await;
+        let snapshots = read_records::<XpSnapshotRecord>(XP_SNAPSHOT_FILE).await;
+        let last_recorded = snapshots.iter().map(|snapshot| snapshot.recorded_at).min();
+
+        let members = leaderboard_members().await;
+
+        // without members there's nothing to record, so the snapshot isn't uploaded every hour
+        if !members.is_empty() && !matches!(last_recorded, Some(recorded_at) if recorded_at + Duration::weeks(1) > Local::now()) {
+            let previous_xp: HashMap<u64, f32> = snapshots
+                .iter()
+                .filter_map(|snapshot| snapshot.xp_point.map(|xp_point| (snapshot.member_id, xp_point)))
+                .collect();
+            let mut current_xp = Vec::new();
+
+            for member_id in members {
+                current_xp.push((member_id, member_xp(&member_id).await));
+            }
+
+            let (xp_gains, new_snapshots) = next_snapshot(&previous_xp, &current_xp, Local::now());
+            post_movers(ctx, &xp_gains).await;
+
+            if let Err(e) = overwrite_records(XP_SNAPSHOT_FILE, &new_snapshots).await {
+                eprintln!("Failed to store the XP snapshot: {:?}", e);
+            }
+        }
+
+        finish_task(XP_MOVERS_TASK).await;
+        tokio::time::sleep(Duration::hours(1).to_std().unwrap()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_snapshot_test() {
+        let previous_xp = HashMap::from([(1, 10.0), (2, 20.0)]);
+        let (xp_gains, snapshots) = next_snapshot(&previous_xp, &[(1, Some(15.0)), (2, None), (3, None)], Local::now());
+
+        assert_eq!(xp_gains, vec![(1, 5.0)]);
+        let xp_points: Vec<(u64, Option<f32>)> = snapshots.iter().map(|snapshot| (snapshot.member_id, snapshot.xp_point)).collect();
+        assert_eq!(xp_points, vec![(1, Some(15.0)), (2, Some(20.0)), (3, None)]);
+    }
+
+    #[test]
+    fn rank_test() {
+        let ranking = rank(vec![(1, 10.0), (2, 30.0), (3, 20.0)]);
+        assert_eq!(ranking, vec![(2, 30.0), (3, 20.0), (1, 10.0)]);
+
+        let ranking = rank((0..20).map(|member_id| (member_id, member_id as f32)).collect());
+        assert_eq!(ranking.len(), LEADERBOARD_SIZE);
+        assert_eq!(ranking[0], (19, 19.0));
+    }
+}
@@ -0,0 +1,12 @@
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+#[macro_use] pub mod locale;
+pub mod api;
+pub mod cli;
+pub mod consts;
+pub mod crypto;
+pub mod discord;
+pub mod third_party;
+
+#[macro_use] extern crate lazy_static;
+#[macro_use] extern crate magic_crypt;
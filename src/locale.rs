@@ -0,0 +1,433 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+
+/// Looks up the message of `key` in the catalog of the language and fills its `{}` in order,
+/// e.g. `tr!(language, "schedule.title", date)`
+macro_rules! tr {
+    ($language:expr, $key:expr) => {
+        $crate::locale::text($language, $key).to_string()
+    };
+    ($language:expr, $key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::locale::fill($crate::locale::text($language, $key), &[$($arg.to_string()),+])
+    };
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    Indonesian,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Indonesian];
+
+    /// The code stored in the guild config and the user language file
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Indonesian => "id",
+        }
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::English => write!(f, "English"),
+            Self::Indonesian => write!(f, "Bahasa Indonesia"),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "en" | "english" => Ok(Language::English),
+            "id" | "indonesian" | "indonesia" | "bahasa" => Ok(Language::Indonesian),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+/// The key, English and Indonesian text of every message
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // accounts
+    ("account.token_expired.title", "Your bearer token has expired", "Bearer token kamu sudah kedaluwarsa"),
+    ("account.token_expired.body", "please re-register using `{}add {}` command", "silakan daftar ulang dengan perintah `{}add {}`"),
+    ("account.not_registered.title", "You're not registered", "Kamu belum terdaftar"),
+    ("account.not_registered.body", "please register first using `{}register` command", "silakan daftar dulu dengan perintah `{}register`"),
+    ("account.not_found.title", "Account not found", "Akun tidak ditemukan"),
+    ("account.not_found.body", "You don't have a new binusmaya account named **{}**, use `{}account list` to see your accounts", "Kamu tidak punya akun new binusmaya bernama **{}**, gunakan `{}account list` untuk melihat akun-akunmu"),
+    ("account.old_not_registered.body", "You haven't registered your **{}** account yet, use `{}register` command to register your account", "Kamu belum mendaftarkan akun **{}**, gunakan perintah `{}register` untuk mendaftarkan akunmu"),
+    ("account.not_found_any.body", "You don't have an account named **{}**, use `{}account list` to see your accounts", "Kamu tidak punya akun bernama **{}**, gunakan `{}account list` untuk melihat akun-akunmu"),
+    ("account.list.title", "Your Accounts", "Akun-akunmu"),
+    ("account.list.none", "No account registered", "Belum ada akun yang terdaftar"),
+    ("account.list.active", " (active)", " (aktif)"),
+    ("account.list.token_expiry", "token expires on {}", "token kedaluwarsa pada {}"),
+    ("account.list.footer", "Use `{}account use [name]` to switch your active account", "Gunakan `{}account use [nama]` untuk mengganti akun aktifmu"),
+    ("account.switched.title", "Account switched", "Akun diganti"),
+    ("account.switched.body", "BINUSMAYA commands will now use your **{}** account", "Perintah BINUSMAYA sekarang menggunakan akun **{}** kamu"),
+    ("account.missing_name.title", "Missing account name", "Nama akun belum diisi"),
+    ("account.missing_name.body", "Please enter the account name, e.g. `{}account use staff`", "Silakan masukkan nama akun, misalnya `{}account use staff`"),
+    ("account.login_failed.title", "Couldn't Log In", "Gagal Masuk"),
+    ("account.login_failed.body", "The bot couldn't log in with your stored credential, please re-register using `{}add {}` command", "Bot gagal masuk dengan kredensial yang tersimpan, silakan daftar ulang dengan perintah `{}add {}`"),
+    ("account.old_login_failed.title", "Couldn't Log In To Old Binusmaya", "Gagal Masuk ke Binusmaya Lama"),
+    ("account.old_login_failed.body", "The bot couldn't log in to your **{}** account with your stored credential, please re-register using `{}add {}` command", "Bot gagal masuk ke akun **{}** kamu dengan kredensial yang tersimpan, silakan daftar ulang dengan perintah `{}add {}`"),
+    ("account.unavailable.body", "Old binusmaya couldn't be reached, please try again later", "Binusmaya lama tidak dapat dihubungi, silakan coba lagi nanti"),
+    ("account.refresh_failed.title", "Couldn't Refresh Your Token", "Gagal Memperbarui Token"),
+    ("account.refresh_failed.body", "The bot couldn't log in to new binusmaya with the stored credential of your **{}** account, please re-register using `{}add {}` command before your token expires", "Bot gagal masuk ke new binusmaya dengan kredensial akun **{}** yang tersimpan, silakan daftar ulang dengan perintah `{}add {}` sebelum token kamu kedaluwarsa"),
+    // registration
+    ("register.title", "Register", "Daftar"),
+    ("register.body", "Use `{}add` command to register your BINUS account, or `{}add [account name]` to register another account", "Gunakan perintah `{}add` untuk mendaftarkan akun BINUS kamu, atau `{}add [nama akun]` untuk mendaftarkan akun lain"),
+    ("add.invalid_name.title", "Invalid account name", "Nama akun tidak valid"),
+    ("add.invalid_name.body", "Account names can only contain letters, numbers, `-` and `_`", "Nama akun hanya boleh berisi huruf, angka, `-` dan `_`"),
+    ("add.choose_version.title", "Choose Binusmaya Version", "Pilih Versi Binusmaya"),
+    ("add.choose_version.body", "Please choose Binusmaya version you want to register to.", "Silakan pilih versi Binusmaya yang ingin kamu daftarkan."),
+    ("add.choose_version.placeholder", "Choose version of Binusmaya", "Pilih versi Binusmaya"),
+    ("add.auto_refresh.title", "Automatic Token Refresh", "Pembaruan Token Otomatis"),
+    ("add.auto_refresh.body", "Your bearer token expires a year after you register. If you let the bot store your encrypted email and password, it will log in again before the token expires so you don't have to re-register.", "Bearer token kamu kedaluwarsa setahun setelah kamu mendaftar. Jika kamu mengizinkan bot menyimpan email dan password kamu yang terenkripsi, bot akan masuk lagi sebelum token kedaluwarsa sehingga kamu tidak perlu mendaftar ulang."),
+    ("add.store_credential", "Refresh my token automatically", "Perbarui tokenku secara otomatis"),
+    ("add.skip_credential", "Don't store my credential", "Jangan simpan kredensialku"),
+    ("add.timed_out", "Timed Out, please try again", "Waktu habis, silakan coba lagi"),
+    ("add.enter_credential", "Please enter your Binus email and password with format of `[email] [password]`", "Silakan masukkan email Binus dan password kamu dengan format `[email] [password]`"),
+    ("add.registering.title", "Registering...", "Mendaftarkan..."),
+    ("add.registering.body", "Please wait a few seconds", "Mohon tunggu beberapa detik"),
+    ("add.already_registered.title", "You've already registered", "Kamu sudah terdaftar"),
+    ("add.already_registered.body", "Please wait **{} days** to re-register your **{}** account", "Mohon tunggu **{} hari** untuk mendaftarkan ulang akun **{}** kamu"),
+    ("add.missing_credential.title", "Error", "Galat"),
+    ("add.missing_credential.body", "Missing email or password", "Email atau password belum diisi"),
+    ("add.registered", "Account Registered", "Akun Terdaftar"),
+    ("add.invalid", "Account is not valid", "Akun tidak valid"),
+    ("add.error.title", "Error in registering", "Gagal mendaftar"),
+    ("add.error.body", "Error in registering, please try again. If the problem still persist, please contact `PlayerPlay#9549` or open a new issue [here](https://github.com/BryanEgbert/BINUSMAYA_Discord_Bot/issues)", "Gagal mendaftar, silakan coba lagi. Jika masalah masih berlanjut, silakan hubungi `PlayerPlay#9549` atau buka issue baru [di sini](https://github.com/BryanEgbert/BINUSMAYA_Discord_Bot/issues)"),
+    // commands
+    ("command.invalid_argument.title", "Invalid argument", "Argumen tidak valid"),
+    ("command.invalid_argument.body", "Use `{}help {}` to see how to use this command", "Gunakan `{}help {}` untuk melihat cara menggunakan perintah ini"),
+    ("command.missing_permission.title", "Missing permission", "Tidak punya izin"),
+    ("command.disabled.title", "Command disabled", "Perintah dinonaktifkan"),
+    ("command.disabled.body", "**{}** commands are disabled in this server", "Perintah **{}** dinonaktifkan di server ini"),
+    ("command.not_found.title", "Couldn't find command name", "Perintah tidak ditemukan"),
+    ("command.not_found.body", "Couldn't find command named **{}**, use `{}help` command to see the command list", "Tidak ada perintah bernama **{}**, gunakan perintah `{}help` untuk melihat daftar perintah"),
+    // components
+    ("pagination.page", "page", "halaman"),
+    ("pagination.footer", "page {}/{}", "halaman {}/{}"),
+    ("interaction.not_yours", "This isn't your menu, only {} can use it. Use the command yourself to get your own", "Ini bukan menu kamu, hanya {} yang bisa menggunakannya. Gunakan perintahnya sendiri untuk mendapatkan menumu"),
+    ("menu.academic_period", "Choose academic period", "Pilih periode akademik"),
+    ("menu.academic_period.placeholder", "No academic period selected", "Belum ada periode akademik yang dipilih"),
+    ("menu.class_component", "Choose class component", "Pilih komponen kelas"),
+    ("menu.course", "Choose course", "Pilih mata kuliah"),
+    // language
+    ("language.title", "Language", "Bahasa"),
+    ("language.current", "Your language: **{}**\nServer language: **{}**", "Bahasamu: **{}**\nBahasa server: **{}**"),
+    ("language.not_set", "not set", "belum diatur"),
+    ("language.updated", "Your language is now **{}**", "Bahasamu sekarang **{}**"),
+    ("language.reset", "Your language now follows the server, or English in DM", "Bahasamu sekarang mengikuti server, atau bahasa Inggris di DM"),
+    ("language.unsupported.title", "Unsupported language", "Bahasa tidak didukung"),
+    ("language.unsupported.body", "Supported languages: {}", "Bahasa yang didukung: {}"),
+    // schedule
+    ("schedule.title", "Schedule for {}", "Jadwal {}"),
+    ("schedule.sessions", "**{} Session(s)**", "**{} Sesi**"),
+    ("schedule.holiday.title", "Holiday!", "Libur!"),
+    ("schedule.holiday.body", "No classes/sessions for today", "Tidak ada kelas/sesi hari ini"),
+    ("schedule.invalid_date.title", "Invalid date", "Tanggal tidak valid"),
+    ("schedule.invalid_date.body", "`{}` isn't a date, use `YYYY-MM-DD`, `today`, `tomorrow`, `yesterday`, a day name like `monday` or `next week`", "`{}` bukan tanggal, gunakan `YYYY-MM-DD`, `hari ini`, `besok`, `kemarin`, nama hari seperti `senin` atau `minggu depan`"),
+    ("schedule.today", "Today", "Hari ini"),
+    ("schedule.next_week", "+7 days", "+7 hari"),
+    ("schedule.class_title", "Class Title", "Judul Kelas"),
+    ("schedule.subject", "Subject", "Mata Kuliah"),
+    ("schedule.start", "Start", "Mulai"),
+    ("schedule.end", "End", "Selesai"),
+    ("schedule.location", "Location", "Lokasi"),
+    // sessions
+    ("session.title", "{}\nSession {}", "{}\nSesi {}"),
+    ("session.session", "Session", "Sesi"),
+    ("session.footer", "session {}/{}", "sesi {}/{}"),
+    ("session.link", "Session link", "Tautan sesi"),
+    ("session.zoom_link", "Class Zoom Link", "Tautan Zoom Kelas"),
+    ("session.no_link", "No link", "Tidak ada tautan"),
+    ("session.lecturers", "Lecturers", "Dosen"),
+    ("session.subtopics", "Subtopics", "Subtopik"),
+    ("session.resources", "Resources", "Materi"),
+    ("session.delivery_mode", "Delivery Mode", "Mode Perkuliahan"),
+    ("session.class_delivery_mode", "Class Delivery Mode", "Mode Perkuliahan Kelas"),
+    ("session.choose_number.title", "Choose Session Number", "Pilih Nomor Sesi"),
+    ("session.choose_number.body", "Choose session number from 1 - {}", "Pilih nomor sesi dari 1 - {}"),
+    ("session.topic", "Topic", "Topik"),
+    ("session.starts_at", "Starts At", "Mulai Pada"),
+    ("session.join", "Join Class", "Masuk Kelas"),
+    ("session.class_footer", "Class {}", "Kelas {}"),
+    ("session.none", "This class has no session yet", "Kelas ini belum memiliki sesi"),
+    ("materials.none", "This session has no downloadable resource", "Sesi ini tidak memiliki materi yang bisa diunduh"),
+    ("materials.summary", "{} file(s) zipped, {} resource(s) linked below", "{} berkas di-zip, {} materi ditautkan di bawah"),
+    ("session.choose_number.footer", "Timeout in 30 seconds, type cancel to cancel operation", "Batas waktu 30 detik, ketik cancel untuk membatalkan"),
+    ("resource.name", "Name", "Nama"),
+    ("resource.duration", "Duration", "Durasi"),
+    ("resource.type", "Type", "Tipe"),
+    ("resource.minutes", "{} min", "{} menit"),
+    ("progress.status", "Status", "Status"),
+    ("progress.completed", "Completed", "Selesai"),
+    ("progress.in_progress", "In progress", "Sedang dikerjakan"),
+    ("progress.not_started", "Not started", "Belum dimulai"),
+    ("progress.incomplete", "Incomplete", "Belum selesai"),
+    ("progress.title", "Course Progress", "Progres Mata Kuliah"),
+    ("progress.note", "Only the unfinished resources of sessions that already started are listed", "Hanya materi yang belum selesai dari sesi yang sudah dimulai yang ditampilkan"),
+    ("progress.summary", "({}/{} completed, {} in progress)", "({}/{} selesai, {} sedang dikerjakan)"),
+    ("progress.more", "and {} more", "dan {} lainnya"),
+    // classes
+    ("classes.title", "Class List", "Daftar Kelas"),
+    ("classes.none", "No active class", "Tidak ada kelas aktif"),
+    ("classes.no_match", "No active class matches the course", "Tidak ada kelas aktif yang cocok dengan mata kuliah tersebut"),
+    ("class.class_code", "Class code", "Kode kelas"),
+    ("class.course_code", "Course code", "Kode mata kuliah"),
+    ("class.course_name", "Course name", "Nama mata kuliah"),
+    ("class.component", "Class component", "Komponen kelas"),
+    ("class.class_id", "Class id", "Id kelas"),
+    ("ongoing.title", "Ongoing Sessions", "Sesi Berlangsung"),
+    ("ongoing.count", "**{} Ongoing Session(s)**", "**{} Sesi Berlangsung**"),
+    ("ongoing.time_left", "Time Left", "Sisa Waktu"),
+    ("upcoming.title", "Upcoming Session", "Sesi Berikutnya"),
+    ("upcoming.none", "No upcoming session", "Tidak ada sesi berikutnya"),
+    ("upcoming.session_info", "Session Info", "Info Sesi"),
+    ("upcoming.time_start", "Time Start", "Waktu Mulai"),
+    // reminders
+    ("reminder.forum.title", "Post GSLC Forum Reminder", "Pengingat Forum GSLC"),
+    ("reminder.forum.body", "Don't forget to post a forum, today's the deadline.", "Jangan lupa mengisi forum, hari ini batas waktunya."),
+    ("reminder.forum.link", "forum link", "tautan forum"),
+    // announcements
+    ("lecturers.title", "Lecturers", "Dosen"),
+    ("lecturers.unknown", "Unknown", "Tidak diketahui"),
+    ("lecturers.none", "No lecturer assigned yet", "Belum ada dosen yang ditugaskan"),
+    ("lecturers.details", "> Role: **{}**\n> Lecturer code: **{}**", "> Peran: **{}**\n> Kode dosen: **{}**"),
+    // config
+    ("config.title", "Server Settings", "Pengaturan Server"),
+    ("config.updated", "Server Settings Updated", "Pengaturan Server Diperbarui"),
+    ("config.footer", "Use `{}help config` to see how to change the settings", "Gunakan `{}help config` untuk melihat cara mengubah pengaturan"),
+    ("config.prefix", "Prefix: ", "Prefix: "),
+    ("config.language", "Language: ", "Bahasa: "),
+    ("config.reminder_channel", "Reminder channel: ", "Kanal pengingat: "),
+    ("config.admin_role", "Bot admin role: ", "Role admin bot: "),
+    ("config.class_roles", "Class roles: ", "Role kelas: "),
+    ("config.course_channels", "Course channels: ", "Kanal mata kuliah: "),
+    ("config.interaction_timeout", "Interaction timeout: ", "Batas waktu interaksi: "),
+    ("config.minutes", "{} minute(s)", "{} menit"),
+    ("config.command_groups", "Command groups:", "Grup perintah:"),
+    ("config.on", "on", "aktif"),
+    ("config.off", "off", "nonaktif"),
+    ("config.enabled", "enabled", "diaktifkan"),
+    ("config.disabled", "disabled", "dinonaktifkan"),
+    ("config.channel_not_found.title", "Channel not found", "Kanal tidak ditemukan"),
+    ("config.channel_not_found.body", "Please mention a text channel of this server, e.g. `{}config reminder_channel #reminders`", "Silakan mention kanal teks server ini, misalnya `{}config reminder_channel #reminders`"),
+    ("config.role_not_found.title", "Role not found", "Role tidak ditemukan"),
+    ("config.role_not_found.body", "Please mention a role of this server, e.g. `{}config admin_role @Admin`", "Silakan mention role server ini, misalnya `{}config admin_role @Admin`"),
+    ("config.invalid_timeout.title", "Invalid timeout", "Batas waktu tidak valid"),
+    ("config.invalid_timeout.body", "The timeout is in minutes, from 1 to {}", "Batas waktu dalam menit, dari 1 sampai {}"),
+    ("config.group_not_found.title", "Command group not found", "Grup perintah tidak ditemukan"),
+    ("config.group_not_found.body", "Command groups: `{}`", "Grup perintah: `{}`"),
+    ("config.invalid_setting.title", "Invalid setting", "Pengaturan tidak valid"),
+    ("config.invalid_setting.body", "Use `{}help config` to see the available settings", "Gunakan `{}help config` untuk melihat pengaturan yang tersedia"),
+    // subscriptions
+    ("subscribe.title", "Mirrored Classes", "Kelas yang Dicerminkan"),
+    ("subscribe.none", "No class is mirrored in this server", "Tidak ada kelas yang dicerminkan di server ini"),
+    ("subscribe.token_expired", " (token expired)", " (token kedaluwarsa)"),
+    ("subscribe.data_from", " `{}` - data from ", " `{}` - data dari "),
+    ("subscribe.not_found.title", "Class not found", "Kelas tidak ditemukan"),
+    ("subscribe.not_found.body", "This class isn't mirrored in this server, use `{}subscribe list` to see the mirrored classes", "Kelas ini tidak dicerminkan di server ini, gunakan `{}subscribe list` untuk melihat kelas yang dicerminkan"),
+    ("subscribe.removed.title", "Class removed", "Kelas dihapus"),
+    ("subscribe.removed.body", "Sessions of class `{}` won't be posted anymore", "Sesi kelas `{}` tidak akan dikirim lagi"),
+    ("subscribe.already.title", "Already mirrored", "Sudah dicerminkan"),
+    ("subscribe.already.body", "This class is already mirrored in this channel", "Kelas ini sudah dicerminkan di kanal ini"),
+    ("subscribe.consent.title", "Data Source Needed", "Butuh Sumber Data"),
+    ("subscribe.consent.body", "The bot needs a registered member who takes class `{}` to read its sessions and announcements with their new binusmaya token. Press the button below to let the bot use your active account, only the sessions of this class and the announcements that mention its course are posted.", "Bot butuh anggota terdaftar yang mengambil kelas `{}` untuk membaca sesi dan pengumumannya dengan token new binusmaya mereka. Tekan tombol di bawah agar bot menggunakan akun aktifmu, hanya sesi kelas ini dan pengumuman yang menyebut mata kuliahnya yang dikirim."),
+    ("subscribe.consent.footer", "Timeout in {} minute(s)", "Batas waktu {} menit"),
+    ("subscribe.consent.button", "Use my account", "Gunakan akunku"),
+    ("subscribe.consent.unreadable", "Your active new binusmaya account can't read this class, make sure you're registered and take this class", "Akun new binusmaya aktifmu tidak bisa membaca kelas ini, pastikan kamu terdaftar dan mengambil kelas ini"),
+    ("subscribe.mirrored.title", "Class Mirrored", "Kelas Dicerminkan"),
+    ("subscribe.mirrored.body", "Sessions of **{} {}** will be posted in this channel {} minutes before they start, along with the announcements that mention the course, using the account of {}", "Sesi **{} {}** akan dikirim di kanal ini {} menit sebelum dimulai, beserta pengumuman yang menyebut mata kuliahnya, menggunakan akun {}"),
+    ("subscribe.timed_out.title", "Timed out", "Waktu habis"),
+    ("subscribe.timed_out.body", "No one agreed to be the data source, please try again", "Tidak ada yang bersedia menjadi sumber data, silakan coba lagi"),
+    ("subscribe.missing_permission", "Only members who can manage the server or have the bot admin role can change the mirrored classes", "Hanya anggota yang dapat mengelola server atau memiliki role admin bot yang dapat mengubah kelas yang dicerminkan"),
+    // classmates
+    ("classmates.title", "Classmates", "Teman Sekelas"),
+    ("classmates.footer", "Ask them to form a study group or a team for your group assignments", "Ajak mereka membentuk kelompok belajar atau tim untuk tugas kelompokmu"),
+    ("classmates.discoverable.title", "You're discoverable", "Kamu bisa ditemukan"),
+    ("classmates.discoverable.body", "Other discoverable members of this server can now see that you share a class or course with them, use `{}classmates optout` to hide yourself", "Anggota lain server ini yang bisa ditemukan sekarang dapat melihat bahwa kalian berada di kelas atau mata kuliah yang sama, gunakan `{}classmates optout` untuk menyembunyikan dirimu"),
+    ("classmates.hidden.title", "You're hidden", "Kamu disembunyikan"),
+    ("classmates.hidden.body", "You won't be listed to your classmates in this server anymore", "Kamu tidak akan ditampilkan lagi ke teman sekelasmu di server ini"),
+    ("classmates.not_discoverable.title", "You're not discoverable", "Kamu tidak bisa ditemukan"),
+    ("classmates.not_discoverable.body", "Only members who share their classes can see their classmates, use `{}classmates optin` first", "Hanya anggota yang membagikan kelasnya yang bisa melihat teman sekelasnya, gunakan `{}classmates optin` terlebih dahulu"),
+    ("classmates.no_classes.title", "Couldn't get your classes", "Gagal mengambil kelasmu"),
+    ("classmates.no_classes.body", "Make sure your active new binusmaya account is registered and its token hasn't expired", "Pastikan akun new binusmaya aktifmu sudah terdaftar dan tokennya belum kedaluwarsa"),
+    ("classmates.none", "No discoverable classmates yet", "Belum ada teman sekelas yang bisa ditemukan"),
+    ("classmates.same_class", "Same class: {}", "Kelas yang sama: {}"),
+    ("classmates.same_course", "Same course: {}", "Mata kuliah yang sama: {}"),
+    // profile
+    ("profile.hidden", "||hidden, use this command in DM||", "||disembunyikan, gunakan perintah ini di DM||"),
+    ("profile.person_code", "Person code: ", "Kode person: "),
+    ("profile.email", "Email: ", "Email: "),
+    ("profile.active_role", "Active role: ", "Peran aktif: "),
+    ("profile.programme", "Programme: ", "Program studi: "),
+    ("profile.career", "Career: ", "Jenjang: "),
+    ("profile.institution", "Institution: ", "Institusi: "),
+    ("profile.nim", "NIM: ", "NIM: "),
+    ("profile.unavailable", "Couldn't get your profile, make sure your account is registered with `{}add` and its token hasn't expired", "Gagal mengambil profilmu, pastikan akunmu sudah didaftarkan dengan `{}add` dan tokennya belum kedaluwarsa"),
+    ("profile.token_valid", "Registered, token expires on {}", "Terdaftar, token kedaluwarsa pada {}"),
+    ("profile.token_expired", "Token expired on {}", "Token kedaluwarsa pada {}"),
+    ("profile.registered", "Registered", "Terdaftar"),
+    ("profile.login_failed", "Registered, but the bot couldn't log in", "Terdaftar, tetapi bot gagal masuk"),
+    ("profile.not_registered", "Not registered", "Belum terdaftar"),
+    ("profile.footer", "{} account", "akun {}"),
+    // xp
+    ("leaderboard.title", "XP Leaderboard", "Papan Peringkat XP"),
+    ("leaderboard.joined.title", "You joined the leaderboard", "Kamu bergabung ke papan peringkat"),
+    ("leaderboard.joined.body", "Your XP is shown in the leaderboard of the servers you're in, use `{}leaderboard optout` to leave it", "XP kamu ditampilkan di papan peringkat server yang kamu ikuti, gunakan `{}leaderboard optout` untuk keluar"),
+    ("leaderboard.left.title", "You left the leaderboard", "Kamu keluar dari papan peringkat"),
+    ("leaderboard.left.body", "Your XP won't be shown anymore", "XP kamu tidak akan ditampilkan lagi"),
+    ("leaderboard.empty.title", "The leaderboard is empty", "Papan peringkat masih kosong"),
+    ("leaderboard.empty.body", "Use `{}leaderboard optin` to be the first one", "Gunakan `{}leaderboard optin` untuk menjadi yang pertama"),
+    ("leaderboard.movers.title", "Weekly XP Movers", "Kenaikan XP Mingguan"),
+    ("leaderboard.movers.footer", "Use `{}leaderboard optin` to join the leaderboard", "Gunakan `{}leaderboard optin` untuk bergabung ke papan peringkat"),
+    ("xp.title", "XP Points", "Poin XP"),
+    ("xp.body", "{} has **{} XP**", "{} memiliki **{} XP**"),
+    ("xp.footer", "Use `{}leaderboard optin` to compete with the members of your server", "Gunakan `{}leaderboard optin` untuk bersaing dengan anggota servermu"),
+    ("announcement.title", "Announcements", "Pengumuman"),
+    ("announcement.search_title", "Search Results", "Hasil Pencarian"),
+    ("announcement.none", "No announcement found", "Tidak ada pengumuman"),
+    ("announcement.footer", "page {}/{}, {} announcement(s), 🔵 unread", "halaman {}/{}, {} pengumuman, 🔵 belum dibaca"),
+    ("announcement.select", "Choose an announcement to read", "Pilih pengumuman untuk dibaca"),
+    ("announcement.invalid_filter", "Invalid Filter", "Filter Tidak Valid"),
+    ("announcement.missing_value", "`{}` needs a value", "`{}` perlu diberi nilai"),
+    ("announcement.invalid_date", "`{}` is not a date, use YYYY-MM-DD", "`{}` bukan tanggal, gunakan YYYY-MM-DD"),
+    ("announcement.not_found.title", "Announcement Not Found", "Pengumuman Tidak Ditemukan"),
+    ("announcement.not_found.body", "Couldn't get the details of this announcement, please try again later", "Gagal mengambil detail pengumuman ini, silakan coba lagi nanti"),
+    ("announcement.mandatory", "Mandatory", "Wajib"),
+    ("announcement.link", "Link", "Tautan"),
+    ("common.yes", "Yes", "Ya"),
+    ("common.no", "No", "Tidak"),
+    ("common.new_binusmaya", "New Binusmaya", "Binusmaya Baru"),
+    ("common.old_binusmaya", "Old Binusmaya", "Binusmaya Lama"),
+    // old binusmaya
+    ("assignment.individual", "Individual", "Individu"),
+    ("assignment.group", "Group", "Kelompok"),
+    ("assignment.individual_title", "Individual Assignment(s)", "Tugas Individu"),
+    ("assignment.group_title", "Group Assignment(s)", "Tugas Kelompok"),
+    ("assignment.get_files", "Get question files", "Ambil berkas soal"),
+    ("assignment.none", "No assignment", "Tidak ada tugas"),
+    ("assignment.title", "Title", "Judul"),
+    ("assignment.due", "Due datetime", "Tenggat waktu"),
+    ("assignment.too_large", "These files are too large to be uploaded, open them while you're logged in to binusmaya", "Berkas-berkas ini terlalu besar untuk diunggah, buka saat kamu sudah masuk ke binusmaya"),
+    ("sat.title", "SAT Points", "Poin SAT"),
+    ("sat.points", "**{}** point(s)", "**{}** poin"),
+    ("comserv.title", "Community Service", "Pengabdian Masyarakat"),
+    ("comserv.hours", "**{}** hour(s)", "**{}** jam"),
+    // admin
+    ("admin.maintenance", "Maintenance Notice", "Pemberitahuan Pemeliharaan"),
+];
+
+const EN_DAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+const ID_DAYS: [&str; 7] = ["Senin", "Selasa", "Rabu", "Kamis", "Jumat", "Sabtu", "Minggu"];
+const EN_MONTHS: [&str; 12] = ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+const ID_MONTHS: [&str; 12] = ["Januari", "Februari", "Maret", "April", "Mei", "Juni", "Juli", "Agustus", "September", "Oktober", "November", "Desember"];
+
+lazy_static! {
+    static ref CATALOG: HashMap<&'static str, (&'static str, &'static str)> = MESSAGES
+        .iter()
+        .map(|(key, english, indonesian)| (*key, (*english, *indonesian)))
+        .collect();
+}
+
+/// The message of `key`, the key itself if the catalog doesn't have it
+pub fn text(language: Language, key: &'static str) -> &'static str {
+    match (CATALOG.get(key), language) {
+        (Some((english, _)), Language::English) => english,
+        (Some((_, indonesian)), Language::Indonesian) => indonesian,
+        (None, _) => key,
+    }
+}
+
+/// Replaces the `{}` of the template with the arguments in order
+pub fn fill(template: &str, args: &[String]) -> String {
+    let mut parts = template.split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+
+    for (i, part) in parts.enumerate() {
+        filled.push_str(args.get(i).map_or("{}", |arg| arg.as_str()));
+        filled.push_str(part);
+    }
+
+    filled
+}
+
+/// The day names of the language, monday first
+pub fn day_names(language: Language) -> [&'static str; 7] {
+    match language {
+        Language::English => EN_DAYS,
+        Language::Indonesian => ID_DAYS,
+    }
+}
+
+/// e.g. `Wednesday, 5 January 2022` or `Rabu, 5 Januari 2022`
+pub fn format_date(language: Language, date: NaiveDate) -> String {
+    let months = match language {
+        Language::English => EN_MONTHS,
+        Language::Indonesian => ID_MONTHS,
+    };
+
+    format!(
+        "{}, {} {} {}",
+        day_names(language)[date.weekday().num_days_from_monday() as usize],
+        date.day(),
+        months[date.month0() as usize],
+        date.year()
+    )
+}
+
+/// e.g. `Wednesday, 5 January 2022 07:20`
+pub fn format_datetime(language: Language, datetime: NaiveDateTime) -> String {
+    format!("{} {}", format_date(language, datetime.date()), datetime.format("%H:%M"))
+}
+
+/// Formats the `2022-01-05T07:20:00` dates of the binusmaya APIs, other formats are kept as they are
+pub fn format_api_datetime(language: Language, datetime: &str) -> String {
+    match NaiveDateTime::parse_from_str(datetime, "%FT%X") {
+        Ok(datetime) => format_datetime(language, datetime),
+        Err(_) => datetime.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_test() {
+        assert_eq!(CATALOG.len(), MESSAGES.len(), "message keys must be unique");
+
+        for (key, english, indonesian) in MESSAGES {
+            assert_eq!(english.matches("{}").count(), indonesian.matches("{}").count(), "placeholders of {} don't match", key);
+        }
+    }
+
+    #[test]
+    fn tr_test() {
+        assert_eq!(tr!(Language::Indonesian, "schedule.holiday.title"), "Libur!");
+        assert_eq!(tr!(Language::English, "session.footer", 2, 13), "session 2/13");
+        assert_eq!(tr!(Language::Indonesian, "session.footer", 2, 13), "sesi 2/13");
+        assert_eq!(tr!(Language::English, "missing.key"), "missing.key");
+        assert_eq!(fill("{} of {}", &["1".to_string()]), "1 of {}");
+    }
+
+    #[test]
+    fn format_date_test() {
+        let datetime = NaiveDateTime::parse_from_str("2022-01-05T07:20:00", "%FT%X").unwrap();
+
+        assert_eq!(format_datetime(Language::English, datetime), "Wednesday, 5 January 2022 07:20");
+        assert_eq!(format_api_datetime(Language::Indonesian, "2022-01-05T07:20:00"), "Rabu, 5 Januari 2022 07:20");
+        assert_eq!(format_api_datetime(Language::Indonesian, "tomorrow"), "tomorrow");
+        assert_eq!("ID".parse::<Language>(), Ok(Language::Indonesian));
+        assert!("fr".parse::<Language>().is_err());
+    }
+}
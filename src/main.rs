@@ -1,16 +1,11 @@
 #![allow(non_snake_case)]
-#![allow(dead_code)]
-pub mod api;
-pub mod consts;
-pub mod discord;
-pub mod third_party;
-
-#[macro_use] extern crate lazy_static;
-#[macro_use] extern crate magic_crypt;
-
-use discord::discord::run;
+use binusmaya::discord::discord::{rotate_keys, run};
+use std::env;
 
 #[tokio::main]
 async fn main() {
-    run().await;
+    match env::args().nth(1).as_deref() {
+        Some("rotate-keys") => rotate_keys().await,
+        _ => run().await,
+    }
 }
@@ -12,6 +12,12 @@ pub struct BrowserMobProxy {
     pub port: u16,
 }
 
+/// The proxy started with the bot, it captures the bearer token of the headless logins
+pub const BROWSERMOB_PROXY: BrowserMobProxy = BrowserMobProxy {
+    host: "localhost",
+    port: 8082,
+};
+
 pub struct Selenium {
     pub driver: WebDriver,
     pub email: String,